@@ -13,6 +13,7 @@ pub(crate) fn get_all_commands() -> Vec<Command> {
         crate::commands::init::definition(),
         crate::commands::new::definition(),
         crate::commands::placeholder::definition(),
+        crate::commands::config::definition(),
         crate::commands::load::definition(),
         crate::commands::reload::definition(),
         crate::commands::generate::definition(),
@@ -0,0 +1,82 @@
+use crate::log;
+use crate::types::argument::Argument;
+use crate::types::command::Command;
+use crate::types::config::Config;
+use crate::types::status::Status;
+
+/// The definition of the config command.
+pub(crate) fn definition() -> Command {
+    let mut config_command = Command::new(
+        vec!["config".to_string(), "c".to_string()],
+        config,
+        "Manage the user configuration (get, set or list).".to_string(),
+    );
+
+    config_command.add_argument(Argument::new(
+        "action".to_string(),
+        0,
+        true,
+        "The action to perform: get, set or list.".to_string(),
+    ));
+
+    config_command.add_argument(Argument::new(
+        "key".to_string(),
+        1,
+        false,
+        "The key of the config entry (e.g. author.name).".to_string(),
+    ));
+
+    config_command.add_argument(Argument::new(
+        "value".to_string(),
+        2,
+        false,
+        "The new value of the config entry (only for set).".to_string(),
+    ));
+
+    config_command
+}
+
+/// The config command is used to read and write the user configuration.
+pub(crate) fn config(command: &Command) -> Status {
+    let action = command.get_argument("action").value.clone();
+    let key = command.get_argument("key").value.clone();
+    let value = command.get_argument("value").value.clone();
+
+    let mut config = Config::user();
+
+    match action.as_str() {
+        "get" => {
+            if key.is_empty() {
+                return Status::error("Missing argument: key".to_string());
+            }
+            let v = config.get(&key);
+            if v.is_none() {
+                return Status::error(format!("Config entry {} is not set.", key));
+            }
+            log!("{}", v.unwrap());
+        }
+        "set" => {
+            if key.is_empty() {
+                return Status::error("Missing argument: key".to_string());
+            }
+            if !command.get_argument("value").is_set {
+                return Status::error("Missing argument: value".to_string());
+            }
+            config.set(&key, &value);
+            let st = config.save();
+            if !st.is_ok {
+                return st;
+            }
+            log!("Set {} to {}", key, value);
+        }
+        "list" => {
+            log!("Config file: {}", config.get_file_path());
+            for (k, v) in config.list() {
+                log!("  {}: {}", k, v);
+            }
+        }
+        _ => return Status::error(format!("Unknown action: {}. Use get, set or list.", action)),
+    }
+
+    Status::ok()
+}
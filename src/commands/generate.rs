@@ -1,10 +1,10 @@
 use crate::log;
 use crate::types::argument::Argument;
 use crate::types::command::Command;
+use crate::types::config::Config;
 use crate::types::flag::Flag;
 use crate::types::status::Status;
 use crate::{types, utils};
-use std::io::Write;
 
 /// The definition of the generate command.
 pub(crate) fn definition() -> Command {
@@ -59,6 +59,27 @@ pub(crate) fn definition() -> Command {
         "If enabled the template name must match exactly.".to_string(),
     ));
 
+    generate_command.add_flag(Flag::new_bool_flag(
+        vec!["no-hooks".to_string(), "nh".to_string()],
+        "If enabled the hooks and the command of the template are not executed.".to_string(),
+    ));
+
+    generate_command.add_flag(Flag::new_bool_flag(
+        vec!["strict-snippets".to_string(), "ss".to_string()],
+        "If enabled the generation fails if a snippet cannot be applied.".to_string(),
+    ));
+
+    generate_command.add_flag(Flag::new_bool_flag(
+        vec!["here".to_string(), "H".to_string()],
+        "If enabled the files are generated relative to the current directory instead of the project root.".to_string(),
+    ));
+
+    generate_command.add_flag(Flag::new_value_flag(
+        vec!["output".to_string(), "o".to_string()],
+        "".to_string(),
+        "Write the rendered files to stdout, tar:<file> or zip:<file> instead of the project. Hooks and snippets are skipped.".to_string(),
+    ));
+
     generate_command
 }
 
@@ -72,6 +93,23 @@ pub(crate) fn generate(command: &Command) -> Status {
     let strict = command.get_bool_flag("strict");
     let dry_run = command.get_bool_flag("dry-run");
     let force = command.get_bool_flag("force");
+    let strict_snippets = command.get_bool_flag("strict-snippets");
+    let no_hooks = command.get_bool_flag("no-hooks");
+    let here = command.get_bool_flag("here");
+
+    let output = command.get_value_flag("output");
+    let output = if output.trim().is_empty() {
+        None
+    } else {
+        match output.parse::<types::generate_types::OutputTarget>() {
+            Ok(output) => Some(output),
+            Err(e) => return Status::error(e),
+        }
+    };
+    // keep stdout free for the rendered files
+    if output == Some(types::generate_types::OutputTarget::Stdout) {
+        crate::logger::use_stderr();
+    }
 
     let mut template_name = command.get_argument("template-name").value.clone();
     let given_name = command.get_argument("new-name").value.clone();
@@ -86,6 +124,12 @@ pub(crate) fn generate(command: &Command) -> Status {
         utils::template_handler::reload_template(template_name.clone(), strict, false);
     }
 
+    // the project commands have to be confirmed before anything is rendered
+    let st = crate::placeholder_storage::confirm_project_commands();
+    if !st.is_ok {
+        return st;
+    }
+
     let mut meta = types::template_meta::TemplateMeta::parse(template_name.clone().to_string());
     let mut manual_vars = vec![];
     let st = meta
@@ -96,6 +140,21 @@ pub(crate) fn generate(command: &Command) -> Status {
         return st;
     }
 
+    // use the default variable values from the user config
+    let config = Config::user();
+    for placeholder in meta.var_placeholder_collection.get_all_placeholders() {
+        if manual_vars.contains(&placeholder.name) {
+            continue;
+        }
+        if let Some(value) = config.get(&format!("vars.{}", placeholder.name)) {
+            if placeholder.has_options() && !placeholder.options.contains(&value) {
+                continue;
+            }
+            placeholder.set_value(value);
+            manual_vars.push(placeholder.name.clone());
+        }
+    }
+
     let use_default_vars = command.get_bool_flag("default-var");
 
     if use_default_vars {
@@ -134,9 +193,7 @@ pub(crate) fn generate(command: &Command) -> Status {
                 for i in 0..placeholder.options.len() {
                     log!("  [{}] {}", i + 1, placeholder.options[i]);
                 }
-                print!("  > ");
-
-                std::io::stdout().flush().unwrap();
+                crate::logger::write_prompt("  > ");
                 std::io::stdin().read_line(&mut input).unwrap();
                 input = input.trim().to_string();
 
@@ -156,8 +213,7 @@ pub(crate) fn generate(command: &Command) -> Status {
             continue;
         }
 
-        print!(" {}: ", placeholder);
-        std::io::stdout().flush().unwrap();
+        crate::logger::write_prompt(&format!(" {}: ", placeholder));
         std::io::stdin().read_line(&mut input).unwrap();
         input = input.trim().to_string();
 
@@ -179,43 +235,93 @@ pub(crate) fn generate(command: &Command) -> Status {
 
     let mut new_path = meta.get_path();
     new_path = utils::formater::handle_placeholders(&new_path, &given_name, meta.clone());
+    if here {
+        new_path = utils::functions::resolve_invocation_path(&new_path);
+    }
+
+    if let Some(output) = output {
+        return render_output(&output, &given_name, &new_path, &meta);
+    }
+
+    if !force {
+        let existing_files = utils::template_handler::get_existing_files(
+            &meta.get_template_dir(),
+            &new_path,
+            &given_name,
+            &meta,
+        );
+        if !existing_files.is_empty() {
+            for file in existing_files.iter() {
+                log!("File {} already exists.", file);
+            }
+            return Status::error("Files could not be generated.".to_string());
+        }
+    }
 
-    // create dir and all subdirs if they don't exist
+    let st = confirm_hooks(&mut meta, no_hooks, dry_run);
+    if !st.is_ok {
+        return st;
+    }
+
+    let st = meta.check_snippets(strict_snippets);
+    if !st.is_ok {
+        return st;
+    }
+
+    let template_dir = meta.get_template_dir();
+    if dry_run {
+        for hook in meta.pre_generate_hooks.iter() {
+            log!("Would execute pre-generate hook: {}", hook.describe());
+        }
+    } else {
+        let env = types::hook::get_hook_env(&given_name, &meta, &new_path, &[]);
+        for hook in meta.pre_generate_hooks.iter() {
+            let st = hook.run(&template_dir, &new_path, &env);
+            if !st.is_ok {
+                return st;
+            }
+        }
+    }
+
+    // create dir and all subdirs if they don't exist (conflicts were checked above)
     if !dry_run {
         std::fs::create_dir_all(&new_path).unwrap();
     }
 
-    let command =
-        utils::formater::handle_placeholders(&meta.get_command(), &given_name, meta.clone());
-
+    let mut created_files = Vec::new();
     if utils::template_handler::generate_template(
-        &format!(".templates/{}", template_name),
+        &meta.get_template_dir(),
         &new_path,
         given_name.as_str(),
         dry_run,
         meta.clone(),
         force,
+        &mut created_files,
     ) {
         if dry_run {
+            let st = meta.preview_snippets();
+            if !st.is_ok {
+                return st;
+            }
+            for hook in meta.get_post_generate_hooks().iter() {
+                log!("Would execute post-generate hook: {}", hook.describe());
+            }
             log!("Files would be generated successfully.");
             return Status::ok();
         }
-        meta.generate_snippets();
+        let mut formatted_files = created_files.clone();
+        let st = meta.generate_snippets(strict_snippets, &mut formatted_files);
+        if !st.is_ok {
+            return st;
+        }
+        utils::formatters::run_formatters(&formatted_files);
         log!("Files generated successfully.");
 
-        if !command.trim().is_empty() {
-            log!("Executing Command: {}", command);
-
-            match utils::functions::execute_user_command(command) {
-                Ok(result) => {
-                    log!("{}", result);
-                }
-                Err(e) => {
-                    if let Some(stderr) = e.to_string().lines().next() {
-                        log!("{}", stderr);
-                    }
-                    log!("Error executing command: {}", e);
-                }
+        let env = types::hook::get_hook_env(&given_name, &meta, &new_path, &created_files);
+        for hook in meta.get_post_generate_hooks().iter() {
+            let st = hook.run(&template_dir, &new_path, &env);
+            if !st.is_ok {
+                return st;
             }
         }
 
@@ -224,3 +330,101 @@ pub(crate) fn generate(command: &Command) -> Status {
         Status::error("Files could not be generated.".to_string())
     }
 }
+
+/// Render the template to the given output target without touching the project.
+/// Hooks and snippets are skipped because they have side effects on the project.
+fn render_output(
+    output: &types::generate_types::OutputTarget,
+    given_name: &str,
+    new_path: &str,
+    meta: &types::template_meta::TemplateMeta,
+) -> Status {
+    let hooks = meta.get_all_hooks();
+    if !hooks.is_empty() {
+        log!("Skipping {} hook(s) because of -output.", hooks.len());
+    }
+    if !meta.snippets.is_empty() {
+        log!(
+            "Skipping {} snippet(s) because of -output.",
+            meta.snippets.len()
+        );
+    }
+
+    let files = utils::template_handler::render_template(
+        &meta.get_template_dir(),
+        new_path,
+        given_name,
+        meta,
+    );
+    utils::output::write_output(output, &files)
+}
+
+/// Check if the hooks of the template may be executed.
+///
+/// Hooks of templates that were loaded from a remote source (`.source`) have to be confirmed,
+/// unless the user trusted the same version of the template before. Hooks are removed from the
+/// template if `-no-hooks` is set or remote hooks are disabled in the config (`hooks.allow-remote`).
+fn confirm_hooks(
+    meta: &mut types::template_meta::TemplateMeta,
+    no_hooks: bool,
+    dry_run: bool,
+) -> Status {
+    let hooks = meta.get_all_hooks();
+    if hooks.is_empty() {
+        return Status::ok();
+    }
+
+    if no_hooks {
+        log!("Skipping {} hook(s) because of -no-hooks.", hooks.len());
+        meta.disable_hooks();
+        return Status::ok();
+    }
+
+    let source = meta.get_source();
+    if source.trim().is_empty() {
+        return Status::ok();
+    }
+
+    if Config::user().get_bool("hooks.allow-remote") == Some(false) {
+        log!(
+            "Skipping {} hook(s) because hooks of remote templates are disabled (hooks.allow-remote).",
+            hooks.len()
+        );
+        meta.disable_hooks();
+        return Status::ok();
+    }
+
+    let hash = utils::hook_trust::get_template_hash(&meta.get_template_dir());
+    if dry_run || utils::hook_trust::is_trusted(&hash) {
+        return Status::ok();
+    }
+
+    log!(
+        "The template {} was loaded from {} and wants to execute the following hooks:",
+        meta.get_template_name(),
+        source
+    );
+    for hook in hooks.iter() {
+        log!("  {}", hook.describe());
+    }
+
+    let not_confirmed = Status::error(format!(
+        "The hooks of the template {} were not confirmed. Use -no-hooks to generate without them.",
+        meta.get_template_name()
+    ));
+    if !utils::functions::is_interactive() {
+        return not_confirmed;
+    }
+
+    crate::logger::write_prompt(
+        "Do you want to execute them? [y]es / [n]o / [a]lways trust this version: ",
+    );
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input).unwrap();
+
+    match input.trim().to_lowercase().as_str() {
+        "y" | "yes" => Status::ok(),
+        "a" | "always" => utils::hook_trust::trust(&hash, &source),
+        _ => not_confirmed,
+    }
+}
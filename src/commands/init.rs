@@ -28,19 +28,20 @@ pub(crate) fn definition() -> Command {
 pub(crate) fn init(command: &Command) -> Status {
     log!("Initializing templify...");
 
-    // check if .templates folder exists
-    if std::path::Path::new(".templates").exists() {
+    // check if the templates folder exists
+    let templates_dir = utils::functions::get_templates_dir();
+    if std::path::Path::new(&templates_dir).exists() {
         return Status::error("templify is already initialized in this project.".to_string());
     }
 
-    std::fs::create_dir(".templates").unwrap();
+    std::fs::create_dir_all(&templates_dir).unwrap();
 
     if command.get_bool_flag("blank") {
         log!("templify initialized successfully.");
         return Status::ok();
     }
     std::fs::write(
-        ".templates/README.md",
+        format!("{}/README.md", templates_dir),
         crate::data::get_init_readme_content(),
     )
     .unwrap();
@@ -49,7 +50,7 @@ pub(crate) fn init(command: &Command) -> Status {
     if utils::functions::check_internet_connection() && !command.get_bool_flag("offline") {
         log!("Loading example template from templify-vault...");
         let st = utils::template_handler::load_remote_template_collection(
-            ".templates",
+            &templates_dir,
             "https://github.com/cophilot/templify-vault/tree/main/Example",
             true,
         );
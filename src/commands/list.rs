@@ -3,7 +3,9 @@ use crate::types::command::Command;
 use crate::types::flag::Flag;
 use crate::types::status::Status;
 use crate::types::template_meta::TemplateMeta;
-use std::fs::read_dir;
+use crate::types::template_origin::TemplateOrigin;
+use crate::utils::functions::get_global_templates_dir;
+use crate::utils::template_handler::get_templates;
 
 /// The definition of the list command.
 pub(crate) fn definition() -> Command {
@@ -32,31 +34,49 @@ pub(crate) fn list(command: &Command) -> Status {
         return st;
     }
 
-    // get all folders in .templates
-    let paths = read_dir(".templates").unwrap();
-
     let print_path = command.get_bool_flag("path");
     let only_name = command.get_bool_flag("name");
 
+    let templates = get_templates();
     log!("Available templates:");
-    for path in paths {
-        let path = path.unwrap().path();
-        if path.is_dir() {
-            let template_name = path.file_name().unwrap().to_str().unwrap();
-
-            let meta = TemplateMeta::parse(template_name.to_string());
-
-            let mut print_string = template_name.to_string();
-
-            if !meta.get_description().is_empty() && !only_name {
-                print_string = format!("{} - {}", print_string, meta.get_description());
-            }
-            if print_path {
-                print_string = format!("{} [{}]", print_string, meta.get_path());
-            }
-            log!("  {}", print_string);
+    for (template_name, _) in templates
+        .iter()
+        .filter(|(_, o)| *o == TemplateOrigin::Project)
+    {
+        log!(
+            "  {}",
+            describe_template(template_name, only_name, print_path)
+        );
+    }
+
+    let global_templates = templates
+        .iter()
+        .filter(|(_, origin)| *origin == TemplateOrigin::Global)
+        .collect::<Vec<_>>();
+    if !global_templates.is_empty() {
+        log!("Global templates ({}):", get_global_templates_dir());
+        for (template_name, _) in global_templates {
+            log!(
+                "  {}",
+                describe_template(template_name, only_name, print_path)
+            );
         }
     }
 
     Status::ok()
 }
+
+/// Returns the line that describes a template in the list.
+fn describe_template(template_name: &str, only_name: bool, print_path: bool) -> String {
+    let meta = TemplateMeta::parse(template_name.to_string());
+
+    let mut print_string = template_name.to_string();
+
+    if !meta.get_description().is_empty() && !only_name {
+        print_string = format!("{} - {}", print_string, meta.get_description());
+    }
+    if print_path {
+        print_string = format!("{} [{}]", print_string, meta.get_path());
+    }
+    print_string
+}
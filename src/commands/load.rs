@@ -1,6 +1,7 @@
 use crate::log;
 use crate::types::argument::Argument;
 use crate::types::command::Command;
+use crate::types::config::Config;
 use crate::types::flag::Flag;
 use crate::types::status::Status;
 use crate::utils;
@@ -17,7 +18,8 @@ pub(crate) fn definition() -> Command {
         "url".to_string(),
         0,
         true,
-        "The url of the github or gitlab repository.".to_string(),
+        "The url of the github or gitlab repository or the name of a source from the config."
+            .to_string(),
     ));
 
     load_command.add_flag(Flag::new_bool_flag(
@@ -30,6 +32,11 @@ pub(crate) fn definition() -> Command {
         "Load only one template.".to_string(),
     ));
 
+    load_command.add_flag(Flag::new_bool_flag(
+        vec!["global".to_string(), "g".to_string()],
+        "Load the templates into the global template library instead of the project.".to_string(),
+    ));
+
     load_command
 }
 
@@ -40,19 +47,32 @@ pub(crate) fn load(command: &Command) -> Status {
         return Status::error("You need a internet connection for this command!".to_string());
     }
 
-    let st = utils::functions::check_if_templify_initialized();
-    if !st.is_ok {
-        return st;
-    }
+    // the global template library can be used without a project
+    let templates_dir = if command.get_bool_flag("global") {
+        let templates_dir = utils::functions::get_global_templates_dir();
+        if let Err(e) = std::fs::create_dir_all(&templates_dir) {
+            return Status::error(format!(
+                "Failed to create the global template library {}: {}",
+                templates_dir, e
+            ));
+        }
+        templates_dir
+    } else {
+        let st = utils::functions::check_if_templify_initialized();
+        if !st.is_ok {
+            return st;
+        }
+        utils::functions::get_templates_dir()
+    };
 
-    let url = command.get_argument("url").value.clone();
+    let url = resolve_source(command.get_argument("url").value.clone());
 
     let load_template = command.get_bool_flag("template");
     if load_template {
         log!("Loading template from {}...", url);
         let name = url.split('/').last().unwrap();
         let st = utils::template_handler::load_remote_template(
-            format!(".templates/{}", name).as_str(),
+            format!("{}/{}", templates_dir, name).as_str(),
             url.as_str(),
             command.get_bool_flag("force"),
             None,
@@ -63,7 +83,7 @@ pub(crate) fn load(command: &Command) -> Status {
     } else {
         log!("Loading template collection from {}...", url);
         let st = utils::template_handler::load_remote_template_collection(
-            ".templates",
+            templates_dir.as_str(),
             url.as_str(),
             command.get_bool_flag("force"),
         );
@@ -73,3 +93,21 @@ pub(crate) fn load(command: &Command) -> Status {
     }
     Status::ok()
 }
+
+/// Resolve a named template source from the user config (e.g. `vault` or `vault/React-ts`) to its url.
+fn resolve_source(url: String) -> String {
+    if url.starts_with("https://") || url.starts_with("http://") {
+        return url;
+    }
+
+    let config = Config::user();
+    let (name, rest) = match url.split_once('/') {
+        Some((name, rest)) => (name.to_string(), format!("/{}", rest)),
+        None => (url.clone(), String::new()),
+    };
+
+    match config.get(&format!("sources.{}", name)) {
+        Some(source) => format!("{}{}", source.trim_end_matches('/'), rest),
+        None => url,
+    }
+}
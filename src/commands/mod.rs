@@ -1,4 +1,5 @@
 pub mod command_storage;
+pub mod config;
 pub mod generate;
 pub mod help;
 pub mod init;
@@ -51,12 +51,17 @@ pub(crate) fn new(command: &Command) -> Status {
 
     log!("Creating new template: {}", template_name);
 
-    let template_path = format!(".templates/{}", template_name);
+    let template_path = format!(
+        "{}/{}",
+        utils::functions::get_templates_dir(),
+        template_name
+    );
     if std::path::Path::new(&template_path).exists() {
         return Status::error(format!("Template {} already exists.", template_name));
     }
 
-    std::fs::create_dir(&template_path).unwrap();
+    // templates in a namespace (e.g. react/component) are created in nested folders
+    std::fs::create_dir_all(&template_path).unwrap();
 
     std::fs::write(
         format!("{}/.templify.yml", template_path),
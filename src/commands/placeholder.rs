@@ -1,5 +1,8 @@
 use crate::log;
-use crate::placeholder_storage::get_all_placeholders;
+use crate::placeholder_storage::{
+    confirm_project_commands, get_all_param_placeholders, get_all_placeholders,
+    log_placeholder_errors, take_placeholder_errors,
+};
 use crate::types::command::Command;
 use crate::types::status::Status;
 
@@ -14,15 +17,40 @@ pub fn definition() -> Command {
 
 /// Placeholder command implementation.
 pub fn placeholder(_command: &Command) -> Status {
+    let st = confirm_project_commands();
+    if !st.is_ok {
+        return st;
+    }
+
     log!("Available placeholders:");
     for ph in get_all_placeholders() {
+        let custom_marker = if ph.is_custom { " (custom)" } else { "" };
+        if ph.is_contextual {
+            log!("  $${}$$ - {}{}", ph.name, ph.description, custom_marker);
+            continue;
+        }
         log!(
-            "  $${}$$ - {}: {}",
+            "  $${}$$ - {}{}: {}",
             ph.name,
             ph.description,
+            custom_marker,
             (ph.get_value)()
         );
     }
+    for ph in get_all_param_placeholders() {
+        log!(
+            "  $${}:<param>$$ - {}: $${}:{}$$ -> {}",
+            ph.name,
+            ph.description,
+            ph.name,
+            ph.example,
+            (ph.get_value)(&ph.example)
+        );
+    }
+    log_placeholder_errors(take_placeholder_errors());
+    log!("  $$relpath:<path>$$ - The relative path from the generated file to the given path");
+    log!("  $$output-dir$$ - The directory of the generated file");
+    log!("  $$file-path$$ - The path of the generated file");
 
     Status::ok()
 }
@@ -3,8 +3,8 @@ use crate::types::argument::Argument;
 use crate::types::command::Command;
 use crate::types::flag::Flag;
 use crate::types::status::Status;
+use crate::types::template_origin::TemplateOrigin;
 use crate::utils;
-use std::fs::read_dir;
 
 /// The definition of the reload command.
 pub(crate) fn definition() -> Command {
@@ -31,6 +31,11 @@ pub(crate) fn definition() -> Command {
         "If enabled the template will be deleted and reloaded.".to_string(),
     ));
 
+    reload_command.add_flag(Flag::new_bool_flag(
+        vec!["global".to_string(), "g".to_string()],
+        "Reload the templates of the global template library instead of the project (if no name is given).".to_string(),
+    ));
+
     reload_command
 }
 
@@ -41,9 +46,13 @@ pub(crate) fn reload(command: &Command) -> Status {
         return Status::error("You need a internet connection for this command!".to_string());
     }
 
-    let st = utils::functions::check_if_templify_initialized();
-    if !st.is_ok {
-        return st;
+    // the global template library can be reloaded without a project
+    let global = command.get_bool_flag("global");
+    if !global {
+        let st = utils::functions::check_if_templify_initialized();
+        if !st.is_ok {
+            return st;
+        }
     }
     let strict = command.get_bool_flag("strict");
     let reset = command.get_bool_flag("reset");
@@ -52,16 +61,17 @@ pub(crate) fn reload(command: &Command) -> Status {
         return utils::template_handler::reload_template(name, strict, reset);
     }
 
-    let paths: std::fs::ReadDir = read_dir(".templates").unwrap();
-
-    for path in paths {
-        let path = path.unwrap().path();
-        if !path.is_dir() {
-            continue;
-        }
-        let template_name = path.file_name().unwrap().to_str().unwrap();
-
-        let st = utils::template_handler::reload_template(template_name.to_string(), false, reset);
+    let origin = match global {
+        true => TemplateOrigin::Global,
+        false => TemplateOrigin::Project,
+    };
+    let templates = utils::template_handler::get_templates()
+        .into_iter()
+        .filter(|(_, template_origin)| *template_origin == origin)
+        .map(|(template_name, _)| template_name)
+        .collect::<Vec<String>>();
+    for template_name in templates {
+        let st = utils::template_handler::reload_template(template_name.clone(), false, reset);
 
         if !st.is_ok {
             log!(
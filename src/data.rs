@@ -1,4 +1,4 @@
-use crate::placeholder_storage::get_all_placeholders;
+use crate::placeholder_storage::{get_all_param_placeholders, get_all_placeholders};
 
 /// Get the content for a new templify file
 pub fn templify_file_blank(
@@ -13,16 +13,44 @@ pub fn templify_file_blank(
 
 #description: The description of the template 
 #path: The path where the file should be generated based on the project root (you can also use placeholders here)
-#command: The command that will be executed after the template is created. This command will run in the template's directory.
+#command: The command that will be executed after the template is created. It runs in the project root before the post_generate hooks.
+#hooks: # Commands and scripts that are executed before and after the generation
+#   pre_generate:
+#     - echo Generating $TPY_NAME # A command (runs in the project root)
+#   post_generate:
+#     - script: hooks/post.sh # A script file inside the template. It is not copied to the output.
+#       cwd: output # The directory the hook runs in: root (default) or output
+#       continue_on_error: true # Continue the generation if the hook fails. Optional, default is false.
+#       timeout: 5m # Kill the hook if it runs longer (e.g. 30, 30s, 5m or 1h). Optional, default is no timeout.
+#   # The output of hooks is shown while they run. If a hook fails, templify exits with the exit code of the hook.
+#   # Hooks get the environment variables TPY_NAME, TPY_TEMPLATE_NAME, TPY_TEMPLATE_DIR, TPY_OUTPUT_DIR, TPY_PROJECT_ROOT,
+#   # TPY_FILES (the generated files, one per line) and TPY_VAR_<NAME> for each variable.
 #vars: # Define a variable placeholders that can be used in the file content
 #   - package # Variable Placeholder
 #   - subdir(src) # Variable Placeholder with default value
 #   - project[frontend,backend] # Variable Placeholder with list of values
 #snippets:
 #   - id: ID # This is being used to identifiy the exact location of the snippet. The content will be placed to the '~~ID~~' placeholder in the file.
-#     file: src/file.txt # The file where the snippet should be inserted
+#     file: src/file.txt # The file where the snippet should be inserted. Can also be a glob (e.g. src/**/index.ts) or a list of files, relative to the project root.
 #     content: My Content\\nSecond Line Content # The content of the snippet (can be used with placeholders)
+#     auto-indent: true # Indent every line of the content like the line of the placeholder. Optional, default is false.
+#     dedent: true # Strip the common leading whitespace of the content lines. Optional, default is false.
 #     before: true # If the content should be inserted before the placeholder. Optional, default is false.
+#     once: true # Skip the snippet if its content is already present in the file. Optional, default is false.
+#     insert-after: ^const routes # Instead of the placeholder the snippet can be anchored after or before (insert-before or before: <regex>) the first line matching a regex, at the start or end of the file (at: start|end) or inside a block (within: <start regex>...<end regex>). Optional.
+#     match: first # Which matches of the anchor are used: first, last or all. Optional, default is all for the placeholder and first for the other anchors.
+#     required: true # Fail the generation if the file or the anchor cannot be found (see also the -strict-snippets flag). Optional, default is false.
+#     mode: insert # insert (next to the placeholder), replace (replace the placeholder line) or sorted (insert in alphabetical order among the lines of the placeholder's block). Optional, default is insert.
+#   - id: multi-line-snippet
+#     file: src/file.txt
+#     content: | # Multi-line content can be written as a YAML block. In single-line content \\n starts a new line and \\\\n is a literal \\n.
+#       First Line
+#       Second Line
+#   - id: dependency # Structured snippets merge a value into a JSON, YAML or TOML file instead of inserting lines.
+#     file: package.json
+#     path: dependencies.$$name.kebab$$ # The dotted key path in the document (keys with a dot can be quoted, e.g. 'a.b'). Maps are merged, other values are replaced.
+#     value: ^1.0.0 # The value to set (can be a map or a list as well)
+#     format: json # The format of the file. Optional, detected from the file extension by default.
 #   - id: second-snippet
 #     ...
 
@@ -43,7 +71,13 @@ pub fn get_init_readme_content() -> String {
     let mut placeholder_string = String::new();
 
     for ph in get_all_placeholders() {
-        placeholder_string.push_str(&format!("- `$$${}$$`: {}\n", ph.name, ph.description));
+        placeholder_string.push_str(&format!("- `$${}$$`: {}\n", ph.name, ph.description));
+    }
+    for ph in get_all_param_placeholders() {
+        placeholder_string.push_str(&format!(
+            "- `$${}:<param>$$`: {} (e.g. `$${}:{}$$`)\n",
+            ph.name, ph.description, ph.name, ph.example
+        ));
     }
 
     let content = format!("<img src=\"https://raw.githubusercontent.com/cophilot/templify/master/assets/logo.png\" alt=\"\" width=\"30%\"/>
@@ -129,6 +163,12 @@ You can use the following case conversion:
 - `$$<placeholder>.pascal$$`: Pascal case (e.g. `MyComponent`)
 - `$$<placeholder>.macro$$`: Macro case (e.g. `MY_COMPONENT`)
 - `$$<placeholder>.train$$`: Train case (e.g. `My-Component`)
+- `$$<placeholder>.dot$$`: Dot case (e.g. `my.component`)
+- `$$<placeholder>.path$$`: Path case (e.g. `my/component`)
+- `$$<placeholder>.title$$`: Title case (e.g. `My Component`)
+- `$$<placeholder>.sentence$$`: Sentence case (e.g. `My component`)
+- `$$<placeholder>.flat$$`: Flat case (e.g. `mycomponent`)
+- `$$<placeholder>.cobol$$`: COBOL case (e.g. `MY-COMPONENT`)
 
 ---
 
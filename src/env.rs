@@ -1,7 +1,54 @@
 use std::env;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::sync::Mutex;
 
 pub static mut BASE_COMMAND_NAME: String = String::new();
 
+/// The exit code of templify if the command fails.
+static EXIT_CODE: AtomicI32 = AtomicI32::new(1);
+
+/// Set the exit code of templify if the command fails (e.g. the exit code of a failed hook).
+pub fn set_exit_code(code: i32) {
+    EXIT_CODE.store(code, Ordering::SeqCst);
+}
+
+/// Returns the exit code of templify if the command fails.
+pub fn get_exit_code() -> i32 {
+    EXIT_CODE.load(Ordering::SeqCst)
+}
+
+/// The directory templify was started in, if the project root was found in a parent directory.
+static INVOCATION_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Remember the directory templify was started in before changing to the project root.
+pub fn set_invocation_dir(dir: PathBuf) {
+    *INVOCATION_DIR.lock().unwrap() = Some(dir);
+}
+
+/// Returns the directory templify was started in, if it differs from the project root.
+pub fn get_invocation_dir() -> Option<PathBuf> {
+    INVOCATION_DIR.lock().unwrap().clone()
+}
+
+/// Returns and forgets the directory templify was started in.
+pub fn take_invocation_dir() -> Option<PathBuf> {
+    INVOCATION_DIR.lock().unwrap().take()
+}
+
+/// The templates directory given with the `--templates-dir` global flag.
+static TEMPLATES_DIR: Mutex<Option<String>> = Mutex::new(None);
+
+/// Override the templates directory of the project (`--templates-dir`).
+pub fn set_templates_dir(dir: String) {
+    *TEMPLATES_DIR.lock().unwrap() = Some(dir);
+}
+
+/// Returns the templates directory given with the `--templates-dir` global flag.
+pub fn get_templates_dir() -> Option<String> {
+    TEMPLATES_DIR.lock().unwrap().clone()
+}
+
 /// Returns if the current OS is Windows
 pub fn is_windows() -> bool {
     env::consts::OS == "windows"
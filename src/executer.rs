@@ -1,4 +1,12 @@
+use crate::placeholder_storage::reset_placeholder_values;
+use crate::types::config::{reset_user_config, Config};
+use crate::utils::clock;
 use crate::{error, global_flag_storage::get_all_global_flags};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// If the run was already started by processing the global flags, so that the user config
+/// loaded for the flags is kept for the command.
+static RUN_STARTED: AtomicBool = AtomicBool::new(false);
 
 /// This module is responsible for executing a command.
 pub fn execute(args: Vec<String>) -> bool {
@@ -12,7 +20,17 @@ pub fn execute(args: Vec<String>) -> bool {
 
         return false;
     }
+    clock::start_run();
+    crate::env::set_exit_code(1);
+    reset_placeholder_values();
+    if !RUN_STARTED.swap(false, Ordering::SeqCst) {
+        reset_user_config();
+    }
     let execute_status = command.execute();
+    // return to the directory templify was started in if it changed to the project root
+    if let Some(dir) = crate::env::take_invocation_dir() {
+        let _ = std::env::set_current_dir(dir);
+    }
     if !execute_status.is_ok {
         error!("Command execution error: {}", execute_status.message);
         error!(
@@ -27,6 +45,8 @@ pub fn execute(args: Vec<String>) -> bool {
 
 /// Handle the global flags
 pub fn process_global_flags(args: &mut Vec<String>) -> bool {
+    reset_user_config();
+    RUN_STARTED.store(true, Ordering::SeqCst);
     let base_command_name = unsafe { crate::env::BASE_COMMAND_NAME.clone() };
 
     // Parse all global flags
@@ -41,6 +61,14 @@ pub fn process_global_flags(args: &mut Vec<String>) -> bool {
         }
     }
 
+    // Apply the defaults from the user config
+    let config = Config::user();
+    for flag in &mut flags {
+        if let Some(default_value) = config.get(&format!("flags.{}", flag.names[0])) {
+            flag.apply_default(default_value);
+        }
+    }
+
     // Execute callback
     for flag in &mut flags {
         if !flag.is_set {
@@ -1,6 +1,8 @@
 use crate::{
     types::global_flag::GlobalFlag,
-    utils::functions::{handle_dev_mode, handle_log_file, handle_quiet_mode},
+    utils::functions::{
+        handle_dev_mode, handle_log_file, handle_now, handle_quiet_mode, handle_templates_dir,
+    },
 };
 
 /// Get all global flags.
@@ -21,5 +23,16 @@ pub(crate) fn get_all_global_flags() -> Vec<GlobalFlag> {
             handle_log_file,
             "Writes the log output to the specified file.".to_string(),
         ),
+        GlobalFlag::new_value_flag(
+            vec!["now".to_string()],
+            handle_now,
+            "Use the given RFC3339 time (e.g. 2024-01-31T12:00:00Z) for all date placeholders."
+                .to_string(),
+        ),
+        GlobalFlag::new_value_flag(
+            vec!["templates-dir".to_string(), "td".to_string()],
+            handle_templates_dir,
+            "Use the given directory (relative to the project root) for the templates.".to_string(),
+        ),
     ]
 }
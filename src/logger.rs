@@ -1,3 +1,6 @@
+use std::io::Write;
+use std::sync::atomic::{AtomicBool, Ordering};
+
 /// This module provides a simple logging system that can be used to log messages and errors to multiple destinations.
 struct LoggerEntity {
     id: String,
@@ -7,6 +10,9 @@ struct LoggerEntity {
 
 static mut LOGGER_ENTITIES: Vec<LoggerEntity> = Vec::new();
 
+/// If prompts are written to stderr because stdout is used for the output of a command.
+static PROMPT_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
 /// Write a log message to all logger entities.
 pub fn write_log(message: &str) {
     for entity in unsafe { LOGGER_ENTITIES.iter() } {
@@ -57,8 +63,8 @@ pub fn add_logger_entity_closure(id: String, log: Box<dyn Fn(&str)>, error: Box<
     }
 }
 
-/// Remove a logger entity from the system.
-pub fn remove_logger_entity(id: &str) {
+/// Remove a logger entity from the system. Returns if the logger entity existed.
+pub fn remove_logger_entity(id: &str) -> bool {
     let mut index = None;
     for (i, entity) in unsafe { LOGGER_ENTITIES.iter() }.enumerate() {
         if entity.id == id {
@@ -72,6 +78,7 @@ pub fn remove_logger_entity(id: &str) {
             LOGGER_ENTITIES.remove(index);
         }
     }
+    index.is_some()
 }
 
 /// Add the default logger entity that writes to stdout and stderr.
@@ -88,3 +95,30 @@ pub fn use_stdout() {
         |message| println!("{}", message),
     );
 }
+
+/// Replace the default stdout logger entity with one that writes to stderr,
+/// so that stdout can be used for the output of a command.
+pub fn use_stderr() {
+    PROMPT_TO_STDERR.store(true, Ordering::SeqCst);
+    if !remove_logger_entity("stdout") {
+        return;
+    }
+
+    add_logger_entity_fn(
+        "stderr".to_string(),
+        |message| eprintln!("{}", message),
+        |message| eprintln!("{}", message),
+    );
+}
+
+/// Write a prompt for the user input (without a newline). Prompts are written to stderr
+/// if stdout is used for the output of a command (see `use_stderr`).
+pub fn write_prompt(message: &str) {
+    if PROMPT_TO_STDERR.load(Ordering::SeqCst) {
+        eprint!("{}", message);
+        let _ = std::io::stderr().flush();
+    } else {
+        print!("{}", message);
+        let _ = std::io::stdout().flush();
+    }
+}
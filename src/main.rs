@@ -42,5 +42,5 @@ fn exit_when_error(is_success: bool) {
     if is_success {
         return;
     }
-    std::process::exit(1);
+    std::process::exit(env::get_exit_code());
 }
@@ -1,37 +1,489 @@
+use crate::types::config::{yaml_to_string, Config};
+use crate::types::status::Status;
+use crate::utils::clock;
+use crate::utils::functions::{execute_user_command, is_interactive};
+use crate::utils::{formatters, hook_trust};
+use crate::{error, log};
 use crate::{
-    types::placeholder_definition::PlaceholderDefinition, utils::functions::get_git_name,
+    types::placeholder_definition::{ParamPlaceholderDefinition, PlaceholderDefinition},
+    utils::functions::get_author_email,
+    utils::functions::get_git_email,
+    utils::functions::get_git_name,
     utils::functions::get_month_string,
+    utils::functions::get_project_name,
 };
 
-use chrono::Datelike;
+use chrono::format::{Item, StrftimeItems};
+use chrono::{Datelike, SecondsFormat};
+use rand::Rng;
+use std::cell::RefCell;
+use std::sync::{Arc, Mutex, OnceLock};
+use uuid::Uuid;
+use yaml_rust::Yaml;
 
-/// Get all placeholders.
+/// Get all placeholders (built-in and custom ones).
 pub(crate) fn get_all_placeholders() -> Vec<PlaceholderDefinition> {
+    let mut placeholders = get_builtin_placeholders();
+
+    for custom in get_custom_placeholders() {
+        placeholders.retain(|ph| ph.name != custom.name);
+        placeholders.push(custom);
+    }
+
+    placeholders
+}
+
+/// A placeholder of the current run with its memoized value.
+struct RunPlaceholder {
+    definition: PlaceholderDefinition,
+    value: OnceLock<String>,
+}
+
+/// The placeholders of the current run. The values are resolved once per run.
+static RUN_PLACEHOLDERS: Mutex<Option<Arc<Vec<RunPlaceholder>>>> = Mutex::new(None);
+
+thread_local! {
+    /// The errors that occurred while resolving placeholder values on the current thread.
+    /// The logger is not thread-safe, so the errors are collected and logged on the main thread.
+    static PLACEHOLDER_ERRORS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Remember an error that occurred while resolving a placeholder value on the current thread.
+fn report_error(message: String) {
+    PLACEHOLDER_ERRORS.with(|errors| errors.borrow_mut().push(message));
+}
+
+/// Returns (and clears) the errors that occurred while resolving placeholder values on the
+/// current thread. Worker threads return them, so that they can be logged on the main thread.
+pub(crate) fn take_placeholder_errors() -> Vec<String> {
+    PLACEHOLDER_ERRORS.with(|errors| std::mem::take(&mut *errors.borrow_mut()))
+}
+
+/// Log the given placeholder errors. This must only be called on the main thread.
+pub(crate) fn log_placeholder_errors(errors: Vec<String>) {
+    for message in errors {
+        error!("{}", message);
+    }
+}
+
+/// The commands of the project config that the user confirmed for the current run.
+static CONFIRMED_COMMANDS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// If acronyms are preserved in the case conversion of the current run (config `case.keep-acronyms`).
+static KEEP_ACRONYMS: Mutex<Option<bool>> = Mutex::new(None);
+
+/// Reset the memoized placeholder values. This should be called at the start of each run.
+pub(crate) fn reset_placeholder_values() {
+    *RUN_PLACEHOLDERS.lock().unwrap() = None;
+    CONFIRMED_COMMANDS.lock().unwrap().clear();
+    *KEEP_ACRONYMS.lock().unwrap() = None;
+}
+
+/// Returns if acronyms should be preserved in the case conversion. The config is read once per run.
+pub(crate) fn keep_acronyms() -> bool {
+    *KEEP_ACRONYMS.lock().unwrap().get_or_insert_with(|| {
+        Config::user()
+            .get_bool("case.keep-acronyms")
+            .unwrap_or(false)
+    })
+}
+
+/// Get the value of the static placeholder with the given name.
+///
+/// The value is resolved on first use and then reused for the rest of the run, except for
+/// placeholders that get a fresh value for every occurrence (e.g. `$$uuid$$`).
+pub(crate) fn get_placeholder_value(name: &str) -> Option<String> {
+    let placeholders = RUN_PLACEHOLDERS
+        .lock()
+        .unwrap()
+        .get_or_insert_with(|| {
+            Arc::new(
+                get_all_placeholders()
+                    .into_iter()
+                    .map(|definition| RunPlaceholder {
+                        definition,
+                        value: OnceLock::new(),
+                    })
+                    .collect(),
+            )
+        })
+        .clone();
+
+    let ph = placeholders
+        .iter()
+        .find(|ph| ph.definition.name == name && !ph.definition.is_contextual)?;
+    if ph.definition.per_occurrence {
+        return Some((ph.definition.get_value)());
+    }
+    // a skipped command must not be memoized, it can still be confirmed later in the run
+    if let Some(command) = &ph.definition.project_command {
+        if !is_command_confirmed(command) {
+            return Some((ph.definition.get_value)());
+        }
+    }
+    Some(ph.value.get_or_init(|| (ph.definition.get_value)()).clone())
+}
+
+/// Get the built-in placeholders.
+fn get_builtin_placeholders() -> Vec<PlaceholderDefinition> {
     vec![
         PlaceholderDefinition {
             name: "year".to_string(),
             description: "The current year".to_string(),
-            get_value: || chrono::Local::now().year().to_string(),
+            get_value: Box::new(|| clock::now().year().to_string()),
+            is_custom: false,
+            per_occurrence: false,
+            is_contextual: false,
+            project_command: None,
         },
         PlaceholderDefinition {
             name: "month".to_string(),
             description: "The current month".to_string(),
-            get_value: || chrono::Local::now().month().to_string(),
+            get_value: Box::new(|| clock::now().month().to_string()),
+            is_custom: false,
+            per_occurrence: false,
+            is_contextual: false,
+            project_command: None,
         },
         PlaceholderDefinition {
             name: "month-name".to_string(),
             description: "The current month as name".to_string(),
-            get_value: get_month_string,
+            get_value: Box::new(get_month_string),
+            is_custom: false,
+            per_occurrence: false,
+            is_contextual: false,
+            project_command: None,
         },
         PlaceholderDefinition {
             name: "day".to_string(),
             description: "The current day".to_string(),
-            get_value: || chrono::Local::now().day().to_string(),
+            get_value: Box::new(|| clock::now().day().to_string()),
+            is_custom: false,
+            per_occurrence: false,
+            is_contextual: false,
+            project_command: None,
+        },
+        PlaceholderDefinition {
+            name: "timestamp".to_string(),
+            description: "The current Unix timestamp".to_string(),
+            get_value: Box::new(|| clock::now().timestamp().to_string()),
+            is_custom: false,
+            per_occurrence: false,
+            is_contextual: false,
+            project_command: None,
         },
         PlaceholderDefinition {
             name: "git-name".to_string(),
-            description: "The name of the git user".to_string(),
-            get_value: get_git_name,
+            description: "The name of the author (git user)".to_string(),
+            get_value: Box::new(get_git_name),
+            is_custom: false,
+            per_occurrence: false,
+            is_contextual: false,
+            project_command: None,
+        },
+        PlaceholderDefinition {
+            name: "git-email".to_string(),
+            description: "The email of the git user".to_string(),
+            get_value: Box::new(get_git_email),
+            is_custom: false,
+            per_occurrence: false,
+            is_contextual: false,
+            project_command: None,
+        },
+        PlaceholderDefinition {
+            name: "author-email".to_string(),
+            description: "The email of the author".to_string(),
+            get_value: Box::new(get_author_email),
+            is_custom: false,
+            per_occurrence: false,
+            is_contextual: false,
+            project_command: None,
+        },
+        PlaceholderDefinition {
+            name: "project-name".to_string(),
+            description: "The name of the project (from the manifest or the directory)".to_string(),
+            get_value: Box::new(get_project_name),
+            is_custom: false,
+            per_occurrence: false,
+            is_contextual: false,
+            project_command: None,
+        },
+        PlaceholderDefinition {
+            name: "template-name".to_string(),
+            description: "The name of the template that is used for the generation".to_string(),
+            get_value: Box::new(String::new),
+            is_custom: false,
+            per_occurrence: false,
+            is_contextual: true,
+            project_command: None,
+        },
+        PlaceholderDefinition {
+            name: "uuid".to_string(),
+            description: "A random UUID (v4)".to_string(),
+            get_value: Box::new(get_uuid),
+            is_custom: false,
+            per_occurrence: !uuid_per_generation(),
+            is_contextual: false,
+            project_command: None,
         },
     ]
 }
+
+/// Get all placeholders that take a parameter.
+pub(crate) fn get_all_param_placeholders() -> Vec<ParamPlaceholderDefinition> {
+    vec![
+        ParamPlaceholderDefinition {
+            name: "date".to_string(),
+            description: "The current date in the given chrono format".to_string(),
+            example: "%Y-%m-%d".to_string(),
+            get_value: get_formatted_date,
+        },
+        ParamPlaceholderDefinition {
+            name: "timestamp".to_string(),
+            description: "The current timestamp (unix, millis or iso)".to_string(),
+            example: "iso".to_string(),
+            get_value: get_timestamp,
+        },
+        ParamPlaceholderDefinition {
+            name: "random".to_string(),
+            description: "A random hex string with the given length".to_string(),
+            example: "8".to_string(),
+            get_value: get_random_hex,
+        },
+    ]
+}
+
+/// Returns if the `$$uuid$$` placeholder should use one value per generation (config `uuid-mode`).
+fn uuid_per_generation() -> bool {
+    Config::project()
+        .get("uuid-mode")
+        .or(Config::user().get("uuid-mode"))
+        .is_some_and(|mode| mode == "per-generation")
+}
+
+/// Returns a random UUID (v4).
+fn get_uuid() -> String {
+    Uuid::new_v4().to_string()
+}
+
+/// Returns the current date in the given chrono format.
+fn get_formatted_date(format: &str) -> String {
+    if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+        report_error(format!("Invalid date format: {}", format));
+        return String::new();
+    }
+    clock::now().format(format).to_string()
+}
+
+/// Returns the current timestamp in the given format (`unix`, `millis` or `iso`).
+fn get_timestamp(format: &str) -> String {
+    let now = clock::now();
+    match format {
+        "millis" | "ms" => now.timestamp_millis().to_string(),
+        "iso" => now.to_rfc3339_opts(SecondsFormat::Secs, false),
+        _ => now.timestamp().to_string(),
+    }
+}
+
+/// The maximum length of a `$$random:N$$` placeholder.
+const MAX_RANDOM_LENGTH: usize = 1024;
+
+/// Returns a random hex string with the given length.
+fn get_random_hex(length: &str) -> String {
+    let length = match length.trim().parse::<usize>() {
+        Ok(length) if length <= MAX_RANDOM_LENGTH => length,
+        Ok(_) => {
+            report_error(format!(
+                "Invalid length for random: {} (the maximum is {})",
+                length, MAX_RANDOM_LENGTH
+            ));
+            return String::new();
+        }
+        Err(_) => {
+            report_error(format!("Invalid length for random: {}", length));
+            return String::new();
+        }
+    };
+    let mut rng = rand::thread_rng();
+    (0..length)
+        .map(|_| format!("{:x}", rng.gen_range(0..16)))
+        .collect()
+}
+
+/// Get the custom placeholders defined in the `placeholders` section of the user and project config.
+///
+/// A placeholder is either a constant (`company: Acme GmbH`) or a map with a `value` or a
+/// `command` whose output is used as value, and an optional `description`.
+fn get_custom_placeholders() -> Vec<PlaceholderDefinition> {
+    let mut placeholders: Vec<PlaceholderDefinition> = Vec::new();
+
+    for (config, from_project) in [(Config::user(), false), (Config::project(), true)] {
+        let hash = match config.get_yaml("placeholders") {
+            Some(Yaml::Hash(hash)) => hash.clone(),
+            _ => continue,
+        };
+
+        for (key, value) in hash {
+            let name = match key.as_str() {
+                Some(name) => name.to_string(),
+                None => continue,
+            };
+
+            let definition = match value {
+                Yaml::Hash(_) => {
+                    let description = value["description"].as_str().map(|d| d.to_string());
+                    if let Some(command) = value["command"].as_str() {
+                        let command = command.to_string();
+                        let placeholder_name = name.clone();
+                        let project_command = from_project.then(|| command.clone());
+                        PlaceholderDefinition {
+                            name: name.clone(),
+                            description: description
+                                .unwrap_or(format!("The output of `{}`", command)),
+                            get_value: Box::new(move || {
+                                if from_project && !is_command_confirmed(&command) {
+                                    report_error(format!(
+                                        "The command of the placeholder {} from the project config was not confirmed.",
+                                        placeholder_name
+                                    ));
+                                    return String::new();
+                                }
+                                get_command_output(&command)
+                            }),
+                            is_custom: true,
+                            per_occurrence: false,
+                            is_contextual: false,
+                            project_command,
+                        }
+                    } else {
+                        let constant = yaml_to_string(&value["value"]).unwrap_or_default();
+                        PlaceholderDefinition {
+                            name: name.clone(),
+                            description: description.unwrap_or("A custom constant".to_string()),
+                            get_value: Box::new(move || constant.clone()),
+                            is_custom: true,
+                            per_occurrence: false,
+                            is_contextual: false,
+                            project_command: None,
+                        }
+                    }
+                }
+                _ => {
+                    let constant = match yaml_to_string(&value) {
+                        Some(constant) => constant,
+                        None => continue,
+                    };
+                    PlaceholderDefinition {
+                        name: name.clone(),
+                        description: "A custom constant".to_string(),
+                        get_value: Box::new(move || constant.clone()),
+                        is_custom: true,
+                        per_occurrence: false,
+                        is_contextual: false,
+                        project_command: None,
+                    }
+                }
+            };
+
+            placeholders.retain(|ph| ph.name != name);
+            placeholders.push(definition);
+        }
+    }
+
+    placeholders
+}
+
+/// Execute the command of a custom placeholder and return its trimmed output.
+fn get_command_output(command: &str) -> String {
+    match execute_user_command(command.to_string()) {
+        Ok(output) => output.trim().to_string(),
+        Err(e) => {
+            report_error(format!(
+                "Custom placeholder command `{}` failed: {}",
+                command, e
+            ));
+            String::new()
+        }
+    }
+}
+
+/// Returns the commands of the project config (a label and the command): the custom
+/// placeholders that execute a command and the formatters.
+fn get_project_commands() -> Vec<(String, String)> {
+    let mut commands = Vec::new();
+    if let Some(Yaml::Hash(hash)) = Config::project().get_yaml("placeholders") {
+        commands.extend(hash.iter().filter_map(|(key, value)| {
+            let name = key.as_str()?.to_string();
+            let command = value["command"].as_str()?.to_string();
+            Some((format!("$${}$$", name), command))
+        }));
+    }
+    commands.extend(
+        formatters::get_formatter_commands()
+            .into_iter()
+            .map(|(pattern, command)| (format!("formatter {}", pattern), command)),
+    );
+    commands
+}
+
+/// Returns if the command of the project config was confirmed for this run or trusted before.
+pub(crate) fn is_command_confirmed(command: &str) -> bool {
+    CONFIRMED_COMMANDS
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|confirmed| confirmed == command)
+        || hook_trust::is_trusted(&hook_trust::get_command_hash(command))
+}
+
+/// Check if the commands of the project config (custom placeholders and formatters) may be executed.
+///
+/// The project config is part of the repository, so its commands have to be confirmed unless
+/// the user trusted the same command before. Commands of the user config are always executed.
+pub(crate) fn confirm_project_commands() -> Status {
+    let commands = get_project_commands()
+        .into_iter()
+        .filter(|(_, command)| !is_command_confirmed(command))
+        .collect::<Vec<(String, String)>>();
+    if commands.is_empty() {
+        return Status::ok();
+    }
+
+    log!("The project config wants to execute the following commands:");
+    for (label, command) in commands.iter() {
+        log!("  {}: {}", label, command);
+    }
+
+    let not_confirmed = Status::error(
+        "The commands of the project config were not confirmed. Commands of the user config do not need a confirmation."
+            .to_string(),
+    );
+    if !is_interactive() {
+        return not_confirmed;
+    }
+
+    crate::logger::write_prompt(
+        "Do you want to execute them? [y]es / [n]o / [a]lways trust these commands: ",
+    );
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input).unwrap();
+
+    match input.trim().to_lowercase().as_str() {
+        "y" | "yes" => {
+            let mut confirmed = CONFIRMED_COMMANDS.lock().unwrap();
+            confirmed.extend(commands.into_iter().map(|(_, command)| command));
+            Status::ok()
+        }
+        "a" | "always" => {
+            for (label, command) in commands.iter() {
+                let hash = hook_trust::get_command_hash(command);
+                let st = hook_trust::trust(&hash, label);
+                if !st.is_ok {
+                    return st;
+                }
+            }
+            Status::ok()
+        }
+        _ => not_confirmed,
+    }
+}
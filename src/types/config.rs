@@ -0,0 +1,218 @@
+use crate::error;
+use crate::types::status::Status;
+use crate::utils::functions::get_user_config_path;
+use std::sync::Mutex;
+use yaml_rust::yaml::{Hash, Yaml};
+use yaml_rust::{YamlEmitter, YamlLoader};
+
+/// The name of the project configuration file.
+pub(crate) const PROJECT_CONFIG_FILE: &str = ".templifyrc.yml";
+
+/// The user config of the current run, it is loaded on first use.
+static USER_CONFIG: Mutex<Option<Config>> = Mutex::new(None);
+
+/// Forget the user config of the last run. This should be called at the start of each run.
+pub(crate) fn reset_user_config() {
+    *USER_CONFIG.lock().unwrap() = None;
+}
+
+#[derive(Clone)]
+/// A templify configuration file (e.g. the user-level `~/.config/templify/config.yml`).
+pub(crate) struct Config {
+    file_path: String,
+    yaml: Yaml,
+}
+
+impl Config {
+    /// Load the configuration from the given file. A missing file results in an empty configuration.
+    pub fn load(file_path: String) -> Config {
+        let mut config = Config {
+            file_path: file_path.clone(),
+            yaml: Yaml::Hash(Hash::new()),
+        };
+
+        let file_content = match std::fs::read_to_string(&file_path) {
+            Ok(content) => content,
+            Err(_) => return config,
+        };
+
+        match YamlLoader::load_from_str(&file_content) {
+            Ok(docs) => {
+                if let Some(Yaml::Hash(hash)) = docs.into_iter().next() {
+                    config.yaml = Yaml::Hash(hash);
+                }
+            }
+            Err(e) => {
+                error!("Could not parse the config file {}: {}", file_path, e);
+            }
+        }
+
+        config
+    }
+
+    /// Returns the user-level configuration. It is loaded once per run and then shared.
+    pub fn user() -> Config {
+        let path = get_user_config_path();
+        if let Some(config) = USER_CONFIG.lock().unwrap().as_ref() {
+            if config.file_path == path {
+                return config.clone();
+            }
+        }
+        let config = Config::load(path);
+        *USER_CONFIG.lock().unwrap() = Some(config.clone());
+        config
+    }
+
+    /// Load the project configuration (`.templifyrc.yml` in the project root).
+    pub fn project() -> Config {
+        Config::load(PROJECT_CONFIG_FILE.to_string())
+    }
+
+    /// Returns the path of the configuration file.
+    pub fn get_file_path(&self) -> String {
+        self.file_path.clone()
+    }
+
+    /// Returns the raw YAML node for the given dotted key (e.g. `author.name`).
+    pub fn get_yaml(&self, key: &str) -> Option<&Yaml> {
+        let mut node = &self.yaml;
+        for part in key.split('.') {
+            node = match node {
+                Yaml::Hash(hash) => hash.get(&Yaml::String(part.to_string()))?,
+                _ => return None,
+            };
+        }
+        Some(node)
+    }
+
+    /// Returns the value for the given dotted key as a string, if it is a scalar.
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.get_yaml(key).and_then(yaml_to_string)
+    }
+
+    /// Returns the value for the given dotted key as a bool.
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        match self.get_yaml(key)? {
+            Yaml::Boolean(b) => Some(*b),
+            Yaml::String(s) => match s.to_lowercase().as_str() {
+                "true" | "yes" | "on" => Some(true),
+                "false" | "no" | "off" => Some(false),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Set the value for the given dotted key. Missing sections are created.
+    pub fn set(&mut self, key: &str, value: &str) {
+        let parts = key.split('.').collect::<Vec<&str>>();
+        let mut node = &mut self.yaml;
+
+        for (i, part) in parts.iter().enumerate() {
+            if !matches!(node, Yaml::Hash(_)) {
+                *node = Yaml::Hash(Hash::new());
+            }
+            let hash = match node {
+                Yaml::Hash(hash) => hash,
+                _ => unreachable!(),
+            };
+            let yaml_key = Yaml::String(part.to_string());
+
+            if i == parts.len() - 1 {
+                hash.insert(yaml_key, parse_scalar(value));
+                return;
+            }
+            node = hash.entry(yaml_key).or_insert(Yaml::Hash(Hash::new()));
+        }
+    }
+
+    /// Returns all entries of the configuration as flat `(dotted key, value)` pairs.
+    pub fn list(&self) -> Vec<(String, String)> {
+        let mut result = Vec::new();
+        flatten_yaml("", &self.yaml, &mut result);
+        result
+    }
+
+    /// Write the configuration back to its file.
+    pub fn save(&self) -> Status {
+        let path = std::path::Path::new(&self.file_path);
+        if let Some(parent) = path.parent() {
+            if !parent.as_os_str().is_empty() && std::fs::create_dir_all(parent).is_err() {
+                return Status::error(format!(
+                    "Failed to create the config directory: {}",
+                    parent.display()
+                ));
+            }
+        }
+
+        let mut content = String::new();
+        if YamlEmitter::new(&mut content).dump(&self.yaml).is_err() {
+            return Status::error("Failed to serialize the config.".to_string());
+        }
+        let content = content.trim_start_matches("---").trim_start().to_string();
+
+        if let Err(e) = std::fs::write(&self.file_path, format!("{}\n", content)) {
+            return Status::error(format!(
+                "Failed to write the config file {}: {}",
+                self.file_path, e
+            ));
+        }
+        // keep the shared user config up to date
+        let mut user_config = USER_CONFIG.lock().unwrap();
+        if user_config
+            .as_ref()
+            .is_some_and(|config| config.file_path == self.file_path)
+        {
+            *user_config = Some(self.clone());
+        }
+        Status::ok()
+    }
+}
+
+/// Convert a scalar YAML node to a string.
+pub(crate) fn yaml_to_string(yaml: &Yaml) -> Option<String> {
+    match yaml {
+        Yaml::String(s) => Some(s.clone()),
+        Yaml::Integer(i) => Some(i.to_string()),
+        Yaml::Real(r) => Some(r.clone()),
+        Yaml::Boolean(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+/// Parse a value given on the command line into a typed YAML scalar.
+fn parse_scalar(value: &str) -> Yaml {
+    match YamlLoader::load_from_str(value) {
+        Ok(docs) => match docs.into_iter().next() {
+            Some(yaml @ (Yaml::Boolean(_) | Yaml::Integer(_) | Yaml::Real(_))) => yaml,
+            _ => Yaml::String(value.to_string()),
+        },
+        Err(_) => Yaml::String(value.to_string()),
+    }
+}
+
+/// Recursively collect all scalar entries of a YAML node as dotted keys.
+fn flatten_yaml(prefix: &str, yaml: &Yaml, result: &mut Vec<(String, String)>) {
+    match yaml {
+        Yaml::Hash(hash) => {
+            for (k, v) in hash {
+                let k = yaml_to_string(k).unwrap_or_default();
+                let key = if prefix.is_empty() {
+                    k
+                } else {
+                    format!("{}.{}", prefix, k)
+                };
+                flatten_yaml(&key, v, result);
+            }
+        }
+        Yaml::Array(items) => {
+            let values = items.iter().filter_map(yaml_to_string).collect::<Vec<_>>();
+            result.push((prefix.to_string(), values.join(",")));
+        }
+        _ => {
+            if let Some(v) = yaml_to_string(yaml) {
+                result.push((prefix.to_string(), v));
+            }
+        }
+    }
+}
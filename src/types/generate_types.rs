@@ -1,6 +1,77 @@
+use crate::utils::functions::normalize_path;
+use std::str::FromStr;
+
 /// The Definition of Files to be created
 pub(crate) struct FileToCreate {
     pub path: String,
     pub is_dir: bool,
     pub file_content: Option<String>,
 }
+
+#[derive(Clone, Debug, PartialEq)]
+/// Where the rendered files of a template are written to instead of the filesystem (`-output`).
+pub(crate) enum OutputTarget {
+    /// Print the files to stdout.
+    Stdout,
+    /// Package the files into the given tar archive.
+    Tar(String),
+    /// Package the files into the given zip archive.
+    Zip(String),
+}
+
+impl FromStr for OutputTarget {
+    type Err = String;
+
+    /// Parse an output target from its flag value (`stdout`, `tar:<file>` or `zip:<file>`).
+    fn from_str(output: &str) -> Result<OutputTarget, String> {
+        let output = output.trim();
+        if output == "stdout" || output == "-" {
+            return Ok(OutputTarget::Stdout);
+        }
+        let invalid = || {
+            format!(
+                "Invalid output '{}'. Use stdout, tar:<file> or zip:<file>.",
+                output
+            )
+        };
+        let (kind, file) = output.split_once(':').ok_or_else(invalid)?;
+        let file = file.trim();
+        if file.is_empty() {
+            return Err(invalid());
+        }
+        match kind.trim() {
+            "tar" => Ok(OutputTarget::Tar(file.to_string())),
+            "zip" => Ok(OutputTarget::Zip(file.to_string())),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// A file or directory of a template together with its target path
+pub(crate) struct TemplateEntry {
+    pub source_path: String,
+    pub target_path: String,
+    pub is_dir: bool,
+}
+
+/// The location of the file that is currently generated (used for the location placeholders)
+pub(crate) struct FileContext {
+    pub file_path: String,
+}
+
+impl FileContext {
+    /// Create a new file context for the given file path (relative to the project root).
+    pub fn new(file_path: &str) -> FileContext {
+        FileContext {
+            file_path: normalize_path(file_path),
+        }
+    }
+
+    /// Returns the directory of the file (relative to the project root).
+    pub fn get_output_dir(&self) -> String {
+        match self.file_path.rsplit_once('/') {
+            Some((dir, _)) => dir.to_string(),
+            None => ".".to_string(),
+        }
+    }
+}
@@ -98,6 +98,25 @@ impl GlobalFlag {
         Status::ok()
     }
 
+    /// Apply a default value (e.g. from the user config) if the flag was not set on the command line
+    pub fn apply_default(&mut self, default_value: String) {
+        if self.is_set {
+            return;
+        }
+        if self.is_bool {
+            if !matches!(default_value.to_lowercase().as_str(), "true" | "yes" | "on") {
+                return;
+            }
+            self.bool_value = true;
+        } else {
+            if default_value.is_empty() {
+                return;
+            }
+            self.value = default_value;
+        }
+        self.is_set = true;
+    }
+
     /// Get the help string for the flag.
     pub fn to_help_string(&self) -> String {
         let mut help_string = String::new();
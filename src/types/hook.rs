@@ -0,0 +1,266 @@
+use crate::log;
+use crate::types::status::Status;
+use crate::types::template_meta::TemplateMeta;
+use crate::utils;
+use crate::utils::functions::{normalize_path, shell_quote};
+use std::time::Duration;
+use yaml_rust::Yaml;
+
+#[derive(Clone, Debug, PartialEq)]
+/// The directory a hook is executed in.
+pub(crate) enum HookDir {
+    /// The root of the project (default).
+    ProjectRoot,
+    /// The directory the files are generated to.
+    OutputDir,
+}
+
+#[derive(Clone, Debug)]
+/// A command or a script of the template that is executed before or after the generation.
+pub(crate) struct Hook {
+    pub command: String,
+    pub script: Option<String>,
+    pub working_dir: HookDir,
+    pub continue_on_error: bool,
+    pub timeout: Option<Duration>,
+}
+
+impl Hook {
+    /// Create a hook for the given command that runs in the project root.
+    pub fn from_command(command: String, continue_on_error: bool) -> Hook {
+        Hook {
+            command,
+            script: None,
+            working_dir: HookDir::ProjectRoot,
+            continue_on_error,
+            timeout: None,
+        }
+    }
+
+    /// Get a new Hook instance from a YAML object. A hook is either a command string or a map
+    /// with a `command` or a `script` and the optional keys `cwd`, `continue_on_error` and `timeout`.
+    pub fn from_yaml(yaml: &Yaml) -> Option<Hook> {
+        if let Some(command) = yaml.as_str() {
+            return Some(Hook::from_command(command.to_string(), false));
+        }
+
+        let command = yaml["command"].as_str().unwrap_or("").to_string();
+        let script = yaml["script"].as_str().map(normalize_path);
+        if command.trim().is_empty() && script.is_none() {
+            log!("Ignoring a hook without a command or a script.");
+            return None;
+        }
+
+        let working_dir = match yaml["cwd"].as_str().unwrap_or("root").trim() {
+            "output" | "output-dir" => HookDir::OutputDir,
+            "root" | "project" => HookDir::ProjectRoot,
+            other => {
+                log!(
+                    "Unknown hook directory '{}', using the project root.",
+                    other
+                );
+                HookDir::ProjectRoot
+            }
+        };
+
+        let timeout = match &yaml["timeout"] {
+            Yaml::Integer(seconds) if *seconds > 0 => Some(Duration::from_secs(*seconds as u64)),
+            Yaml::Real(seconds) => seconds
+                .parse::<f64>()
+                .ok()
+                .filter(|seconds| *seconds > 0.0)
+                .map(Duration::from_secs_f64),
+            Yaml::String(timeout) => {
+                let timeout = parse_duration(timeout);
+                if timeout.is_none() {
+                    log!("Invalid hook timeout, the hook runs without a timeout.");
+                }
+                timeout
+            }
+            _ => None,
+        };
+
+        Some(Hook {
+            command,
+            script,
+            working_dir,
+            continue_on_error: yaml["continue_on_error"].as_bool().unwrap_or(false),
+            timeout,
+        })
+    }
+
+    /// Parse a list of hooks (or a single hook) from a YAML object.
+    pub fn list_from_yaml(yaml: &Yaml) -> Vec<Hook> {
+        match yaml {
+            Yaml::Array(hooks) => hooks.iter().filter_map(Hook::from_yaml).collect(),
+            Yaml::BadValue | Yaml::Null => Vec::new(),
+            hook => Hook::from_yaml(hook).into_iter().collect(),
+        }
+    }
+
+    /// Parse the placeholders in the command of the hook.
+    pub fn parse_placeholders(&mut self, name: &str, meta: TemplateMeta) {
+        self.command = utils::formater::handle_placeholders(&self.command, name, meta);
+    }
+
+    /// Returns a short description of the hook used in messages.
+    pub fn describe(&self) -> String {
+        match &self.script {
+            Some(script) => format!("Script: {}", script),
+            None => format!("Command: {}", self.command),
+        }
+    }
+
+    /// Run the hook. Scripts are resolved relative to the given template directory.
+    pub fn run(&self, template_dir: &str, output_dir: &str, env: &[(String, String)]) -> Status {
+        log!("Executing {}", self.describe());
+
+        match self.execute(template_dir, output_dir, env) {
+            Ok(()) => Status::ok(),
+            Err((message, _)) if self.continue_on_error => {
+                log!("{}", message);
+                Status::ok()
+            }
+            Err((message, exit_code)) => {
+                crate::env::set_exit_code(exit_code);
+                Status::error(message)
+            }
+        }
+    }
+
+    /// Execute the command or script of the hook. The output is streamed to the logger.
+    /// On failure the error message and the exit code for templify are returned.
+    fn execute(
+        &self,
+        template_dir: &str,
+        output_dir: &str,
+        env: &[(String, String)],
+    ) -> Result<(), (String, i32)> {
+        let command = match &self.script {
+            Some(script) => {
+                let script_path = std::path::Path::new(template_dir).join(script);
+                let script_path = match std::fs::canonicalize(&script_path) {
+                    Ok(path) => path.to_string_lossy().to_string(),
+                    Err(_) => {
+                        return Err((
+                            format!(
+                                "Error executing hook: the script {} does not exist.",
+                                script
+                            ),
+                            1,
+                        ))
+                    }
+                };
+                get_script_command(&script_path)
+            }
+            None => self.command.clone(),
+        };
+
+        let dir = match self.working_dir {
+            HookDir::ProjectRoot => ".".to_string(),
+            HookDir::OutputDir => output_dir.to_string(),
+        };
+        if std::fs::create_dir_all(&dir).is_err() {
+            return Err((format!("Error executing hook: cannot create {}", dir), 1));
+        }
+
+        match utils::functions::execute_streamed_command(command, &dir, env, self.timeout) {
+            Ok(0) => Ok(()),
+            Ok(code) => Err((
+                format!("Error executing command: exited with code {}", code),
+                if code > 0 { code } else { 1 },
+            )),
+            Err(e) if e.kind() == std::io::ErrorKind::TimedOut => {
+                Err((format!("Error executing command: {}", e), 124))
+            }
+            Err(e) => Err((format!("Error executing command: {}", e), 1)),
+        }
+    }
+}
+
+/// Returns the command that runs the given script. On Unix the interpreter of the shebang
+/// line is used (so the script does not have to be executable), without one it runs with `sh`.
+fn get_script_command(script_path: &str) -> String {
+    let script = shell_quote(script_path);
+    if cfg!(target_os = "windows") {
+        return script;
+    }
+    let content = std::fs::read_to_string(script_path).unwrap_or_default();
+    match content
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix("#!"))
+    {
+        Some(interpreter) if !interpreter.trim().is_empty() => {
+            format!("{} {}", interpreter.trim(), script)
+        }
+        _ => format!("sh {}", script),
+    }
+}
+
+/// Parse a duration like `30`, `30s`, `5m` or `1h`.
+fn parse_duration(duration: &str) -> Option<Duration> {
+    let duration = duration.trim();
+    let (value, factor) = match duration.chars().last()? {
+        's' => (&duration[..duration.len() - 1], 1.0),
+        'm' => (&duration[..duration.len() - 1], 60.0),
+        'h' => (&duration[..duration.len() - 1], 3600.0),
+        _ => (duration, 1.0),
+    };
+    let seconds = value.trim().parse::<f64>().ok()?;
+    if seconds <= 0.0 {
+        return None;
+    }
+    Some(Duration::from_secs_f64(seconds * factor))
+}
+
+/// Returns the `TPY_*` environment variables that are passed to the hooks.
+pub(crate) fn get_hook_env(
+    name: &str,
+    meta: &TemplateMeta,
+    output_dir: &str,
+    files: &[String],
+) -> Vec<(String, String)> {
+    let project_root = std::env::current_dir()
+        .map(|dir| dir.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    // the paths are absolute, the hooks do not necessarily run in the project root
+    let absolute = |path: &str| {
+        normalize_path(
+            &std::path::Path::new(&project_root)
+                .join(path)
+                .to_string_lossy(),
+        )
+    };
+    let files = files
+        .iter()
+        .map(|file| absolute(file))
+        .collect::<Vec<String>>();
+
+    let mut env = vec![
+        ("TPY_NAME".to_string(), name.to_string()),
+        ("TPY_TEMPLATE_NAME".to_string(), meta.get_template_name()),
+        (
+            "TPY_TEMPLATE_DIR".to_string(),
+            absolute(&meta.get_template_dir()),
+        ),
+        ("TPY_OUTPUT_DIR".to_string(), absolute(output_dir)),
+        ("TPY_PROJECT_ROOT".to_string(), project_root.clone()),
+        ("TPY_FILES".to_string(), files.join("\n")),
+    ];
+    for (var_name, placeholder) in meta.var_placeholder_collection.placeholders.iter() {
+        let env_name = var_name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+        env.push((format!("TPY_VAR_{}", env_name), placeholder.value.clone()));
+    }
+    env
+}
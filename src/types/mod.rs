@@ -1,13 +1,17 @@
 pub mod argument;
 pub mod command;
+pub mod config;
 pub mod flag;
 pub mod generate_types;
 pub mod global_flag;
+pub mod hook;
 pub mod load_types;
 pub mod placeholder_definition;
 pub mod snippet;
 pub mod status;
 pub mod template_meta;
+pub mod template_origin;
+pub mod text_file;
 pub mod var_placeholder;
 pub mod var_placeholder_collection;
 pub mod version_number;
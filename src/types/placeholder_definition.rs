@@ -2,5 +2,19 @@
 pub(crate) struct PlaceholderDefinition {
     pub name: String,
     pub description: String,
-    pub get_value: fn() -> String,
+    pub get_value: Box<dyn Fn() -> String + Send + Sync>,
+    pub is_custom: bool,
+    pub per_occurrence: bool,
+    /// The value depends on the generated template and is resolved by the formatter.
+    pub is_contextual: bool,
+    /// The command of a project config placeholder, which has to be confirmed before it runs.
+    pub project_command: Option<String>,
+}
+
+/// The definition of a placeholder that takes a parameter (e.g. `$$date:%Y-%m-%d$$`).
+pub(crate) struct ParamPlaceholderDefinition {
+    pub name: String,
+    pub description: String,
+    pub example: String,
+    pub get_value: fn(&str) -> String,
 }
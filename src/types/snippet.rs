@@ -1,91 +1,759 @@
 use crate::log;
+use crate::types::status::Status;
 use crate::utils;
 
+use crate::types::generate_types::FileContext;
 use crate::types::template_meta::TemplateMeta;
+use crate::types::text_file::TextFile;
+use crate::utils::structured::{self, StructuredFormat};
+use regex::Regex;
+use std::ops::Range;
+use std::str::FromStr;
+use yaml_rust::Yaml;
+
+#[derive(Clone, Debug, PartialEq)]
+/// The way the content of a snippet is added to the file.
+pub(crate) enum SnippetMode {
+    /// Insert the content next to the anchor (default).
+    Insert,
+    /// Replace the anchor line (or the content of a `within` block) with the content.
+    Replace,
+    /// Insert each line in alphabetical order among the lines of the anchor's block.
+    Sorted,
+}
+
+impl FromStr for SnippetMode {
+    type Err = String;
+
+    /// Parse the mode from its name (an empty name is `Insert`).
+    fn from_str(mode: &str) -> Result<SnippetMode, String> {
+        match mode.trim().to_lowercase().as_str() {
+            "replace" => Ok(SnippetMode::Replace),
+            "sorted" => Ok(SnippetMode::Sorted),
+            "insert" | "" => Ok(SnippetMode::Insert),
+            _ => Err(format!("Unknown snippet mode '{}'", mode)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// The location in the file a snippet is anchored to.
+pub(crate) enum SnippetAnchor {
+    /// The `~~ID~~` marker line (default).
+    Marker,
+    /// After the line matching the regex.
+    After(String),
+    /// Before the line matching the regex.
+    Before(String),
+    /// The start of the file.
+    Start,
+    /// The end of the file.
+    End,
+    /// Inside the block between a line matching the first and a line matching the second regex.
+    Within(String, String),
+}
+
+impl SnippetAnchor {
+    /// Parse the anchor from the `insert-after` (or `after`), `insert-before` (or `before`), `at`
+    /// and `within` keys of a snippet. A boolean `before` is the flag for the marker, not an anchor.
+    pub fn from_yaml(yaml: &yaml_rust::yaml::Yaml) -> SnippetAnchor {
+        if let Some(regex) = yaml["insert-after"].as_str().or(yaml["after"].as_str()) {
+            return SnippetAnchor::After(regex.to_string());
+        }
+        // `before: true` is the marker flag, `before: <regex>` an anchor like `after`
+        if let Some(regex) = yaml["insert-before"].as_str().or(yaml["before"].as_str()) {
+            return SnippetAnchor::Before(regex.to_string());
+        }
+        if let Some(at) = yaml["at"].as_str() {
+            return at.parse().unwrap_or_else(|e| {
+                log!("{}, using the marker.", e);
+                SnippetAnchor::Marker
+            });
+        }
+        if let Some(within) = yaml["within"].as_str() {
+            return match within.split_once("...") {
+                Some((start, end)) => SnippetAnchor::Within(start.to_string(), end.to_string()),
+                None => {
+                    log!(
+                        "Invalid snippet block '{}', expected '<start>...<end>'.",
+                        within
+                    );
+                    SnippetAnchor::Marker
+                }
+            };
+        }
+        SnippetAnchor::Marker
+    }
+
+    /// Returns a short description of the anchor used in messages.
+    pub fn describe(&self, id: &str) -> String {
+        match self {
+            SnippetAnchor::Marker => format!("marker '~~{}~~'", id),
+            SnippetAnchor::After(regex) | SnippetAnchor::Before(regex) => {
+                format!("anchor '{}'", regex)
+            }
+            SnippetAnchor::Start => "start of the file".to_string(),
+            SnippetAnchor::End => "end of the file".to_string(),
+            SnippetAnchor::Within(start, end) => format!("block '{}...{}'", start, end),
+        }
+    }
+}
+
+impl FromStr for SnippetAnchor {
+    type Err = String;
+
+    /// Parse a position of the `at` key (`start` or `end`).
+    fn from_str(at: &str) -> Result<SnippetAnchor, String> {
+        match at.trim() {
+            "start" => Ok(SnippetAnchor::Start),
+            "end" => Ok(SnippetAnchor::End),
+            _ => Err(format!("Unknown snippet position '{}'", at)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// Which of the matches of an anchor are used.
+pub(crate) enum SnippetMatch {
+    /// Only the first match.
+    First,
+    /// Only the last match.
+    Last,
+    /// Every match.
+    All,
+}
+
+impl FromStr for SnippetMatch {
+    type Err = String;
+
+    /// Parse the match option from its name.
+    fn from_str(value: &str) -> Result<SnippetMatch, String> {
+        match value.trim().to_lowercase().as_str() {
+            "first" => Ok(SnippetMatch::First),
+            "last" => Ok(SnippetMatch::Last),
+            "all" => Ok(SnippetMatch::All),
+            _ => Err(format!("Unknown snippet match '{}'", value)),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+/// The outcome of applying a snippet to its file.
+pub(crate) enum SnippetOutcome {
+    /// The content was inserted at the given number of locations.
+    Inserted(usize),
+    /// The content is already present in the file (`once`).
+    AlreadyPresent,
+    /// The anchor (e.g. the `~~ID~~` marker) was not found in the file.
+    AnchorMissing,
+    /// The file of the snippet does not exist.
+    FileMissing,
+}
+
+/// A resolved location in a file where the content of a snippet is added.
+struct SnippetTarget {
+    /// The index of the line the content is inserted at.
+    index: usize,
+    /// The lines that are replaced in replace mode.
+    replace: Range<usize>,
+    /// The block of lines the content is sorted into in sorted mode.
+    block: Range<usize>,
+    /// The indentation of the anchor line, used for `auto-indent`.
+    indent: String,
+}
 
 #[derive(Clone, Debug)]
 /// A snippet is a text/code snippet that can be added to s specific file when gerating from a template.
 pub(crate) struct Snippet {
     pub id: String,
     pub file_name: String,
+    pub file_patterns: Vec<String>,
     pub content: String,
     pub before: bool,
+    pub once: bool,
+    pub mode: SnippetMode,
+    pub anchor: SnippetAnchor,
+    pub match_option: Option<SnippetMatch>,
+    pub required: bool,
+    pub auto_indent: bool,
+    pub dedent: bool,
+    pub document_path: Option<String>,
+    pub value: Yaml,
+    pub format: Option<StructuredFormat>,
 }
 
 impl Snippet {
     /// Get a new Snippet instance from a YAML object.
     pub fn from_yaml(yaml: &yaml_rust::yaml::Yaml) -> Snippet {
         let id = yaml["id"].as_str().unwrap_or("").to_string();
-        let file_name = yaml["file"].as_str().unwrap_or("").to_string();
+        let file_patterns = match &yaml["file"] {
+            yaml_rust::Yaml::Array(files) => files
+                .iter()
+                .filter_map(|f| f.as_str().map(|f| f.to_string()))
+                .collect(),
+            file => vec![file.as_str().unwrap_or("").to_string()],
+        };
+        let file_name = file_patterns.first().cloned().unwrap_or_default();
         let content = yaml["content"].as_str().unwrap_or("").to_string();
         let before = yaml["before"].as_bool().unwrap_or(false);
+        let once = yaml["once"].as_bool().unwrap_or(false);
+        let mode = yaml["mode"]
+            .as_str()
+            .unwrap_or("")
+            .parse()
+            .unwrap_or_else(|e| {
+                log!("{}, using 'insert'.", e);
+                SnippetMode::Insert
+            });
+        let anchor = SnippetAnchor::from_yaml(yaml);
+        let match_option = yaml["match"]
+            .as_str()
+            .and_then(|value| value.parse::<SnippetMatch>().ok());
+        let required = yaml["required"].as_bool().unwrap_or(false);
+        let auto_indent = yaml["auto-indent"].as_bool().unwrap_or(false);
+        let dedent = yaml["dedent"].as_bool().unwrap_or(false);
+        let document_path = yaml["path"].as_str().map(|p| p.to_string());
+        let value = yaml["value"].clone();
+        let format = yaml["format"]
+            .as_str()
+            .and_then(|format| format.parse::<StructuredFormat>().ok());
         Snippet {
             id,
             file_name,
+            file_patterns,
             content,
             before,
+            once,
+            mode,
+            anchor,
+            match_option,
+            required,
+            auto_indent,
+            dedent,
+            document_path,
+            value,
+            format,
         }
     }
 
     /// Generate the snippet and insert it into the file.
-    pub fn generate(self) {
-        if !std::path::Path::new(&self.file_name).exists() {
-            log!(
-                "Cannot create snippet '{}' because the file '{}' does not exist.",
+    pub fn generate(&self) -> Result<SnippetOutcome, Status> {
+        let (outcome, file) = self.apply()?;
+        if let Some(file) = file {
+            if let Err(e) = file.write(&self.file_name) {
+                return Err(Status::error(format!(
+                    "Failed to write the file '{}' of snippet '{}': {}",
+                    self.file_name, self.id, e
+                )));
+            }
+        }
+        Ok(outcome)
+    }
+
+    /// Returns the outcome the snippet would have without changing the file.
+    pub fn check(&self) -> Result<SnippetOutcome, Status> {
+        self.apply().map(|(outcome, _)| outcome)
+    }
+
+    /// Returns the outcome of the snippet and the diff of the changes it would make, without
+    /// changing the file (used for the dry-run).
+    pub fn preview(&self) -> Result<(SnippetOutcome, Vec<String>), Status> {
+        let (outcome, file) = self.apply()?;
+        let diff = match file {
+            Some(new_file) => {
+                let old_lines = TextFile::read(&self.file_name)
+                    .map(|f| f.lines)
+                    .unwrap_or_default();
+                utils::functions::diff_lines(&old_lines, &new_file.lines, 2)
+            }
+            None => Vec::new(),
+        };
+        Ok((outcome, diff))
+    }
+
+    /// Returns a short description of the location the snippet is added to.
+    pub fn describe_location(&self) -> String {
+        match &self.document_path {
+            Some(document_path) => format!("path '{}'", document_path),
+            None => self.anchor.describe(&self.id),
+        }
+    }
+
+    /// Returns if the given outcome should fail the generation.
+    /// This is the case for a missing file or anchor if the snippet is required or `strict` is set.
+    pub fn is_failure(&self, outcome: &SnippetOutcome, strict: bool) -> bool {
+        (self.required || strict)
+            && matches!(
+                outcome,
+                SnippetOutcome::AnchorMissing | SnippetOutcome::FileMissing
+            )
+    }
+
+    /// Returns the message that describes the given outcome of the snippet.
+    pub fn describe_outcome(&self, outcome: &SnippetOutcome) -> String {
+        match outcome {
+            SnippetOutcome::Inserted(1) => format!(
+                "Snippet '{}' inserted into file '{}'.",
+                self.id, self.file_name
+            ),
+            SnippetOutcome::Inserted(count) => format!(
+                "Snippet '{}' inserted into file '{}' ({} times).",
+                self.id, self.file_name, count
+            ),
+            SnippetOutcome::AlreadyPresent => format!(
+                "Snippet '{}' is already present in file '{}'.",
+                self.id, self.file_name
+            ),
+            SnippetOutcome::AnchorMissing => format!(
+                "Could not find the {} of snippet '{}' in file '{}'.",
+                self.anchor.describe(&self.id),
                 self.id,
                 self.file_name
-            );
-            return;
+            ),
+            SnippetOutcome::FileMissing if is_glob(&self.file_name) => format!(
+                "Cannot create snippet '{}' because no file matches '{}'.",
+                self.id, self.file_name
+            ),
+            SnippetOutcome::FileMissing => format!(
+                "Cannot create snippet '{}' because the file '{}' does not exist.",
+                self.id, self.file_name
+            ),
+        }
+    }
+
+    /// Apply the snippet to the content of its file.
+    /// Returns the outcome and the changed file, if the file needs to be written.
+    fn apply(&self) -> Result<(SnippetOutcome, Option<TextFile>), Status> {
+        if !std::path::Path::new(&self.file_name).exists() {
+            return Ok((SnippetOutcome::FileMissing, None));
+        }
+        if let Some(document_path) = &self.document_path {
+            return self.apply_structured(document_path);
+        }
+
+        let mut file = match TextFile::read(&self.file_name) {
+            Ok(file) => file,
+            Err(e) => {
+                return Err(Status::error(format!(
+                    "Failed to read the file '{}' of snippet '{}': {}",
+                    self.file_name, self.id, e
+                )))
+            }
+        };
+        let lines = &mut file.lines;
+        let content_lines = self.get_content_lines();
+
+        if self.once && contains_block(lines, &content_lines) {
+            return Ok((SnippetOutcome::AlreadyPresent, None));
         }
 
-        let raw_id = format!("~~{}~~", self.id);
+        let targets = self.find_targets(lines).map_err(Status::error)?;
+        if targets.is_empty() {
+            return Ok((SnippetOutcome::AnchorMissing, None));
+        }
+
+        // targets are handled from the bottom up so the indices of the remaining ones stay valid
+        for target in targets.iter().rev() {
+            let content_lines = if self.auto_indent {
+                indent_lines(&content_lines, &target.indent)
+            } else {
+                content_lines.clone()
+            };
+            match self.mode {
+                SnippetMode::Insert => {
+                    lines.splice(target.index..target.index, content_lines);
+                }
+                SnippetMode::Replace => {
+                    lines.splice(target.replace.clone(), content_lines);
+                }
+                SnippetMode::Sorted => {
+                    insert_sorted(lines, target.block.clone(), &content_lines);
+                }
+            }
+        }
+
+        Ok((SnippetOutcome::Inserted(targets.len()), Some(file)))
+    }
+
+    /// Merge the value of the snippet into the structured (JSON, YAML or TOML) file at the given path.
+    fn apply_structured(
+        &self,
+        document_path: &str,
+    ) -> Result<(SnippetOutcome, Option<TextFile>), Status> {
+        let format = match self
+            .format
+            .clone()
+            .or_else(|| StructuredFormat::from_file_name(&self.file_name))
+        {
+            Some(format) => format,
+            None => {
+                return Err(Status::error(format!(
+                    "Cannot detect the format of the file '{}' of snippet '{}'. Use format: json, yaml or toml.",
+                    self.file_name, self.id
+                )))
+            }
+        };
+
+        let mut file = match TextFile::read(&self.file_name) {
+            Ok(file) => file,
+            Err(e) => {
+                return Err(Status::error(format!(
+                    "Failed to read the file '{}' of snippet '{}': {}",
+                    self.file_name, self.id, e
+                )))
+            }
+        };
+
+        let path = match structured::parse_path(document_path) {
+            Ok(path) => path,
+            Err(e) => {
+                return Err(Status::error(format!(
+                    "Invalid path of snippet '{}': {}",
+                    self.id, e
+                )))
+            }
+        };
+        match structured::merge_value(&file.lines.join("\n"), &format, &path, &self.value) {
+            Ok(Some(content)) => {
+                file.lines = content.lines().map(|l| l.to_string()).collect();
+                Ok((SnippetOutcome::Inserted(1), Some(file)))
+            }
+            Ok(None) => Ok((SnippetOutcome::AlreadyPresent, None)),
+            Err(e) => Err(Status::error(format!(
+                "Failed to merge snippet '{}' into the file '{}': {}",
+                self.id, self.file_name, e
+            ))),
+        }
+    }
+
+    /// Resolve the anchor of the snippet to the locations in the given lines.
+    fn find_targets(&self, lines: &[String]) -> Result<Vec<SnippetTarget>, String> {
+        let targets = match &self.anchor {
+            SnippetAnchor::Marker => {
+                let raw_id = format!("~~{}~~", self.id);
+                find_lines(lines, |line| line.contains(&raw_id))
+                    .into_iter()
+                    .map(|i| line_target(lines, i, self.before))
+                    .collect()
+            }
+            SnippetAnchor::After(regex) | SnippetAnchor::Before(regex) => {
+                let regex = self.compile_regex(regex)?;
+                let before = matches!(self.anchor, SnippetAnchor::Before(_));
+                find_lines(lines, |line| regex.is_match(line))
+                    .into_iter()
+                    .map(|i| line_target(lines, i, before))
+                    .collect()
+            }
+            SnippetAnchor::Start => vec![SnippetTarget {
+                index: 0,
+                replace: 0..0,
+                block: 0..find_blank_after(lines, 0),
+                indent: String::new(),
+            }],
+            SnippetAnchor::End => vec![SnippetTarget {
+                index: lines.len(),
+                replace: lines.len()..lines.len(),
+                block: find_blank_before(lines, lines.len())..lines.len(),
+                indent: String::new(),
+            }],
+            SnippetAnchor::Within(start, end) => {
+                let start_regex = self.compile_regex(start)?;
+                let end_regex = self.compile_regex(end)?;
+                find_lines(lines, |line| start_regex.is_match(line))
+                    .into_iter()
+                    .filter_map(|s| {
+                        let e = lines[s + 1..]
+                            .iter()
+                            .position(|line| end_regex.is_match(line))?
+                            + s
+                            + 1;
+                        // the content of the block defines the indentation, if there is any
+                        let indent_line = if e > s + 1 { s + 1 } else { e };
+                        Some(SnippetTarget {
+                            index: e,
+                            replace: s + 1..e,
+                            block: s + 1..e,
+                            indent: get_indent(&lines[indent_line]),
+                        })
+                    })
+                    .collect()
+            }
+        };
+
+        let default_match = if self.anchor == SnippetAnchor::Marker {
+            SnippetMatch::All
+        } else {
+            SnippetMatch::First
+        };
+        let mut targets: Vec<SnippetTarget> = targets;
+        match self.match_option.clone().unwrap_or(default_match) {
+            SnippetMatch::First => targets.truncate(1),
+            SnippetMatch::Last => {
+                let last = targets.pop();
+                targets = last.into_iter().collect();
+            }
+            SnippetMatch::All => {}
+        }
+        Ok(targets)
+    }
 
-        let file_content = std::fs::read_to_string(&self.file_name).unwrap();
-        let lines = file_content.lines().collect::<Vec<&str>>();
+    /// Compile a regex of an anchor.
+    fn compile_regex(&self, regex: &str) -> Result<Regex, String> {
+        Regex::new(regex.trim())
+            .map_err(|e| format!("Invalid anchor '{}' in snippet '{}': {}", regex, self.id, e))
+    }
 
-        let mut new_lines = Vec::new();
+    /// Returns the lines of the snippet content.
+    ///
+    /// Multi-line content (e.g. a YAML block scalar) is split on its line breaks. Single-line
+    /// content is split on the escape sequence `\\n`, where `\\\\n` stands for a literal `\\n`.
+    fn get_content_lines(&self) -> Vec<String> {
+        let lines: Vec<String> = if self.content.contains('\n') {
+            let content = self.content.strip_suffix('\n').unwrap_or(&self.content);
+            content.lines().map(|line| line.to_string()).collect()
+        } else {
+            self.content
+                .split("\\\\n")
+                .map(|part| part.replace("\\n", "\n"))
+                .collect::<Vec<String>>()
+                .join("\\n")
+                .split('\n')
+                .map(|line| line.to_string())
+                .collect()
+        };
 
-        for line in lines.iter() {
-            let mut skip_insert = false;
+        if self.dedent {
+            dedent_lines(&lines)
+        } else {
+            lines
+        }
+    }
 
-            if line.contains(&raw_id) {
-                if self.before {
-                    self.clone().add_content(&mut new_lines);
-                } else {
-                    new_lines.push(line.to_string());
-                    self.clone().add_content(&mut new_lines);
-                    skip_insert = true;
+    /// Resolve the file patterns of the snippet (paths or globs relative to the project root)
+    /// and return one snippet per target file with its placeholders parsed.
+    /// This should be done before generating the snippet.
+    pub fn resolve(&self, name: &str, meta: TemplateMeta) -> Vec<Snippet> {
+        let mut file_names: Vec<String> = Vec::new();
+        for pattern in self.file_patterns.iter() {
+            let pattern = utils::formater::handle_placeholders(pattern, name, meta.clone());
+            for file_name in expand_file_pattern(&pattern) {
+                if !file_names.contains(&file_name) {
+                    file_names.push(file_name);
                 }
-                log!(
-                    "Snippet '{}' inserted into file '{}'.",
-                    self.id,
-                    self.file_name
-                );
             }
+        }
+
+        file_names
+            .into_iter()
+            .map(|file_name| {
+                let mut snippet = self.clone();
+                snippet.file_name = file_name;
+                snippet.parse_placeholders(name, meta.clone());
+                snippet
+            })
+            .collect()
+    }
 
-            if !skip_insert {
-                new_lines.push(line.to_string());
+    /// Parse the placeholders in the content and the anchors of the snippet.
+    fn parse_placeholders(&mut self, name: &str, meta: TemplateMeta) {
+        self.content = utils::formater::handle_placeholders_for_file(
+            self.content.as_str(),
+            name,
+            meta.clone(),
+            &FileContext::new(&self.file_name),
+        );
+        if let Some(document_path) = &self.document_path {
+            self.document_path = Some(utils::formater::handle_placeholders(
+                document_path,
+                name,
+                meta.clone(),
+            ));
+        }
+        self.value = render_yaml_placeholders(&self.value, name, &meta);
+        self.anchor = match &self.anchor {
+            SnippetAnchor::After(regex) => {
+                SnippetAnchor::After(utils::formater::handle_placeholders(regex, name, meta))
+            }
+            SnippetAnchor::Before(regex) => {
+                SnippetAnchor::Before(utils::formater::handle_placeholders(regex, name, meta))
             }
+            SnippetAnchor::Within(start, end) => SnippetAnchor::Within(
+                utils::formater::handle_placeholders(start, name, meta.clone()),
+                utils::formater::handle_placeholders(end, name, meta),
+            ),
+            anchor => anchor.clone(),
+        };
+    }
+}
+
+/// Returns the indices of all lines that match the given predicate.
+fn find_lines(lines: &[String], predicate: impl Fn(&str) -> bool) -> Vec<usize> {
+    lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| predicate(line))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Returns the target for content placed before or after the line at the given index.
+fn line_target(lines: &[String], index: usize, before: bool) -> SnippetTarget {
+    if before {
+        SnippetTarget {
+            index,
+            replace: index..index + 1,
+            block: find_blank_before(lines, index)..index,
+            indent: get_indent(&lines[index]),
+        }
+    } else {
+        SnippetTarget {
+            index: index + 1,
+            replace: index..index + 1,
+            block: index + 1..find_blank_after(lines, index + 1),
+            indent: get_indent(&lines[index]),
         }
+    }
+}
+
+/// Returns the index of the first blank line at or after `start` (or the number of lines).
+fn find_blank_after(lines: &[String], start: usize) -> usize {
+    lines[start..]
+        .iter()
+        .position(|l| l.trim().is_empty())
+        .map_or(lines.len(), |i| start + i)
+}
 
-        let new_content = new_lines.join("\n");
+/// Returns the index after the last blank line before `end` (or 0).
+fn find_blank_before(lines: &[String], end: usize) -> usize {
+    lines[..end]
+        .iter()
+        .rposition(|l| l.trim().is_empty())
+        .map_or(0, |i| i + 1)
+}
 
-        std::fs::write(&self.file_name, new_content).unwrap();
+/// Insert the given lines in alphabetical order into the given block of lines.
+fn insert_sorted(lines: &mut Vec<String>, block: Range<usize>, content_lines: &[String]) {
+    let mut block = block;
+    for content_line in content_lines {
+        let index = lines[block.clone()]
+            .iter()
+            .position(|l| l.trim() > content_line.trim())
+            .map_or(block.end, |i| block.start + i);
+        lines.insert(index, content_line.clone());
+        block.end += 1;
     }
+}
+
+/// Returns the leading whitespace of the given line.
+fn get_indent(line: &str) -> String {
+    line.chars().take_while(|c| c.is_whitespace()).collect()
+}
 
-    /// Add the content of the snippet to the given vector. Changes the vector in place.
-    fn add_content(self, vec: &mut Vec<String>) {
-        let content_lines = self.content.split("\\n").collect::<Vec<&str>>();
-        for line in content_lines.iter() {
-            vec.push(line.to_string());
+/// Prefix every non-empty line with the given indentation.
+fn indent_lines(lines: &[String], indent: &str) -> Vec<String> {
+    lines
+        .iter()
+        .map(|line| {
+            if line.trim().is_empty() {
+                line.clone()
+            } else {
+                format!("{}{}", indent, line)
+            }
+        })
+        .collect()
+}
+
+/// Strip the common leading whitespace of all non-empty lines.
+/// Only the identical whitespace prefix is removed (e.g. a tab is not equal to spaces).
+fn dedent_lines(lines: &[String]) -> Vec<String> {
+    let common = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| get_indent(line))
+        .reduce(|common, indent| {
+            common
+                .chars()
+                .zip(indent.chars())
+                .take_while(|(a, b)| a == b)
+                .map(|(c, _)| c)
+                .collect()
+        })
+        .unwrap_or_default();
+    lines
+        .iter()
+        .map(|line| match line.strip_prefix(common.as_str()) {
+            Some(line) => line.to_string(),
+            None => line.trim_start().to_string(),
+        })
+        .collect()
+}
+
+/// Returns if the given lines contain the content lines as a consecutive block.
+/// Leading and trailing whitespace is ignored, so indented occurrences are found as well.
+fn contains_block(lines: &[String], content_lines: &[String]) -> bool {
+    if content_lines.is_empty() || content_lines.len() > lines.len() {
+        return false;
+    }
+    lines.windows(content_lines.len()).any(|window| {
+        window
+            .iter()
+            .zip(content_lines)
+            .all(|(line, content_line)| line.trim() == content_line.trim())
+    })
+}
+
+/// Returns if the given file pattern is a glob.
+fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '['])
+}
+
+/// Returns the files matching the given pattern in alphabetical order.
+/// A pattern without a match is returned as is, so it is reported as missing.
+fn expand_file_pattern(pattern: &str) -> Vec<String> {
+    if !is_glob(pattern) {
+        return vec![pattern.to_string()];
+    }
+
+    let files = match glob::glob(pattern) {
+        Ok(paths) => paths
+            .filter_map(|path| path.ok())
+            .filter(|path| path.is_file())
+            .map(|path| path.to_string_lossy().replace('\\', "/"))
+            .collect::<Vec<String>>(),
+        Err(e) => {
+            log!("Invalid file pattern '{}': {}", pattern, e);
+            Vec::new()
         }
+    };
+
+    if files.is_empty() {
+        return vec![pattern.to_string()];
     }
+    files
+}
 
-    /// Parse the placeholders in the snippet. This should be done before generating the snippet.
-    pub fn parse_placeholders(&mut self, name: &str, meta: TemplateMeta) {
-        self.content =
-            utils::formater::handle_placeholders(self.content.as_str(), name, meta.clone());
-        self.file_name =
-            utils::formater::handle_placeholders(self.file_name.as_str(), name, meta.clone());
+/// Replace the placeholders in all strings (keys and values) of the given YAML value.
+fn render_yaml_placeholders(value: &Yaml, name: &str, meta: &TemplateMeta) -> Yaml {
+    match value {
+        Yaml::String(s) => {
+            Yaml::String(utils::formater::handle_placeholders(s, name, meta.clone()))
+        }
+        Yaml::Array(items) => Yaml::Array(
+            items
+                .iter()
+                .map(|item| render_yaml_placeholders(item, name, meta))
+                .collect(),
+        ),
+        Yaml::Hash(hash) => Yaml::Hash(
+            hash.iter()
+                .map(|(k, v)| {
+                    (
+                        render_yaml_placeholders(k, name, meta),
+                        render_yaml_placeholders(v, name, meta),
+                    )
+                })
+                .collect(),
+        ),
+        other => other.clone(),
     }
 }
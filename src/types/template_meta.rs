@@ -1,8 +1,17 @@
-use crate::types::snippet::Snippet;
+use crate::log;
+use crate::types::hook::Hook;
+use crate::types::snippet::{Snippet, SnippetOutcome};
+use crate::types::status::Status;
 use crate::types::var_placeholder_collection::VarPlaceholderCollection;
+use crate::utils;
+use crate::utils::functions::normalize_path;
 use yaml_rust::yaml::Yaml;
 use yaml_rust::YamlLoader;
 
+/// The file the loader writes the source of a remote template to. Unlike the `.source` key of
+/// the `.templify.yml` it is always written by templify, so a template cannot hide its origin.
+pub(crate) const ORIGIN_FILE: &str = ".templify-origin";
+
 #[derive(Clone)]
 /// The meta information of a template.
 pub(crate) struct TemplateMeta {
@@ -11,6 +20,8 @@ pub(crate) struct TemplateMeta {
     map: std::collections::HashMap<String, String>,
     pub var_placeholder_collection: VarPlaceholderCollection,
     pub snippets: Vec<Snippet>,
+    pub pre_generate_hooks: Vec<Hook>,
+    pub post_generate_hooks: Vec<Hook>,
 }
 
 impl TemplateMeta {
@@ -23,9 +34,10 @@ impl TemplateMeta {
         map.insert(".source".to_string(), "".to_string());
         map.insert("command".to_string(), "".to_string());
 
-        let mut file_path = format!(".templates/{}/.templify.yaml", template_name);
+        let template_dir = utils::template_handler::get_template_dir(&template_name);
+        let mut file_path = format!("{}/.templify.yaml", template_dir);
         if !std::path::Path::new(&file_path).exists() {
-            file_path = format!(".templates/{}/.templify.yml", template_name);
+            file_path = format!("{}/.templify.yml", template_dir);
         }
 
         TemplateMeta {
@@ -34,6 +46,8 @@ impl TemplateMeta {
             map,
             var_placeholder_collection: VarPlaceholderCollection::new(),
             snippets: Vec::new(),
+            pre_generate_hooks: Vec::new(),
+            post_generate_hooks: Vec::new(),
         }
     }
 
@@ -78,6 +92,12 @@ impl TemplateMeta {
                     continue;
                 }
 
+                if k == "hooks" {
+                    meta.pre_generate_hooks = Hook::list_from_yaml(&value["pre_generate"]);
+                    meta.post_generate_hooks = Hook::list_from_yaml(&value["post_generate"]);
+                    continue;
+                }
+
                 if k == "snippets" {
                     if !value.is_array() {
                         // TODO: log error
@@ -105,16 +125,151 @@ impl TemplateMeta {
     /// Parse the placeholders.
     pub fn handle_placeholders(&mut self, name: &str) {
         let self_clone = self.clone();
-        for snippet in self.snippets.iter_mut() {
-            snippet.parse_placeholders(name, self_clone.clone());
+        self.snippets = self
+            .snippets
+            .iter()
+            .flat_map(|snippet| snippet.resolve(name, self_clone.clone()))
+            .collect();
+
+        let command =
+            utils::formater::handle_placeholders(&self.get_command(), name, self_clone.clone());
+        self.map.insert("command".to_string(), command);
+
+        for hook in self
+            .pre_generate_hooks
+            .iter_mut()
+            .chain(self.post_generate_hooks.iter_mut())
+        {
+            hook.parse_placeholders(name, self_clone.clone());
+        }
+    }
+
+    /// Returns the hooks that run after the generation. The `command` of the template runs first.
+    pub fn get_post_generate_hooks(&self) -> Vec<Hook> {
+        let mut hooks = Vec::new();
+        if !self.get_command().trim().is_empty() {
+            hooks.push(Hook::from_command(self.get_command(), false));
+        }
+        hooks.extend(self.post_generate_hooks.iter().cloned());
+        hooks
+    }
+
+    /// Returns all hooks of the template (pre and post generate hooks and the `command`).
+    pub fn get_all_hooks(&self) -> Vec<Hook> {
+        let mut hooks = self.pre_generate_hooks.clone();
+        hooks.extend(self.get_post_generate_hooks());
+        hooks
+    }
+
+    /// Remove all hooks (and the `command`) of the template, so none of them is executed.
+    pub fn disable_hooks(&mut self) {
+        self.pre_generate_hooks.clear();
+        self.post_generate_hooks.clear();
+        self.map.insert("command".to_string(), "".to_string());
+    }
+
+    /// Returns the script files of the hooks (relative to the project root).
+    /// They are part of the template but are not copied to the output.
+    pub fn get_hook_script_paths(&self) -> Vec<String> {
+        self.pre_generate_hooks
+            .iter()
+            .chain(self.post_generate_hooks.iter())
+            .filter_map(|hook| hook.script.as_ref())
+            .map(|script| normalize_path(&format!("{}/{}", self.get_template_dir(), script)))
+            .collect()
+    }
+
+    /// Returns the directory of the template (relative to the project root).
+    pub fn get_template_dir(&self) -> String {
+        match self.file_path.rsplit_once('/') {
+            Some((dir, _)) => dir.to_string(),
+            None => ".".to_string(),
         }
     }
 
-    /// Generate the defined snippets.
-    pub fn generate_snippets(&self) {
+    /// Check the defined snippets before any file is written.
+    /// Fails if a required snippet (or any snippet if `strict` is set) cannot be applied.
+    /// Snippets whose file does not exist yet are skipped, as the file might be generated.
+    pub fn check_snippets(&self, strict: bool) -> Status {
+        let mut failures = Vec::new();
         for snippet in self.snippets.iter() {
-            snippet.clone().generate();
+            let outcome = match snippet.check() {
+                Ok(outcome) => outcome,
+                Err(st) => return st,
+            };
+            if outcome != SnippetOutcome::FileMissing && snippet.is_failure(&outcome, strict) {
+                failures.push(snippet.describe_outcome(&outcome));
+            }
         }
+        snippet_failure_status(failures)
+    }
+
+    /// Log the changes the defined snippets would make, without changing any file.
+    pub fn preview_snippets(&self) -> Status {
+        for snippet in self.snippets.iter() {
+            let (outcome, diff) = match snippet.preview() {
+                Ok(preview) => preview,
+                Err(st) => return st,
+            };
+            match outcome {
+                SnippetOutcome::Inserted(_) => {
+                    log!(
+                        "Would insert snippet '{}' into file '{}' at the {}:",
+                        snippet.id,
+                        snippet.file_name,
+                        snippet.describe_location()
+                    );
+                    for line in diff {
+                        log!("    {}", line);
+                    }
+                }
+                _ => {
+                    log!("Would skip snippet: {}", snippet.describe_outcome(&outcome));
+                }
+            }
+        }
+        Status::ok()
+    }
+
+    /// Generate the defined snippets and log a summary of their outcomes.
+    /// The files that were changed by a snippet are added to `modified_files`.
+    pub fn generate_snippets(&self, strict: bool, modified_files: &mut Vec<String>) -> Status {
+        if self.snippets.is_empty() {
+            return Status::ok();
+        }
+
+        let mut outcomes = Vec::new();
+        for snippet in self.snippets.iter() {
+            match snippet.generate() {
+                Ok(outcome) => {
+                    if matches!(outcome, SnippetOutcome::Inserted(_))
+                        && !modified_files.contains(&snippet.file_name)
+                    {
+                        modified_files.push(snippet.file_name.clone());
+                    }
+                    outcomes.push((snippet, outcome))
+                }
+                Err(st) => return st,
+            }
+        }
+
+        let count = |f: fn(&SnippetOutcome) -> bool| outcomes.iter().filter(|(_, o)| f(o)).count();
+        log!(
+            "Snippets: {} inserted, {} already present, {} anchor missing, {} file missing",
+            count(|o| matches!(o, SnippetOutcome::Inserted(_))),
+            count(|o| *o == SnippetOutcome::AlreadyPresent),
+            count(|o| *o == SnippetOutcome::AnchorMissing),
+            count(|o| *o == SnippetOutcome::FileMissing)
+        );
+        let mut failures = Vec::new();
+        for (snippet, outcome) in outcomes.iter() {
+            let message = snippet.describe_outcome(outcome);
+            log!("  {}", message);
+            if snippet.is_failure(outcome, strict) {
+                failures.push(message);
+            }
+        }
+        snippet_failure_status(failures)
     }
 
     /// Returns the template name.
@@ -132,9 +287,14 @@ impl TemplateMeta {
         self.map["path"].clone()
     }
 
-    /// Returns the source that is specified in the template meta information.
+    /// Returns the source the template was loaded from. The origin file written by the loader
+    /// takes precedence over the `.source` key (templates loaded before the origin file existed).
     pub fn get_source(&self) -> String {
-        self.map[".source"].clone()
+        let origin_path = format!("{}/{}", self.get_template_dir(), ORIGIN_FILE);
+        match std::fs::read_to_string(origin_path) {
+            Ok(origin) if !origin.trim().is_empty() => origin.trim().to_string(),
+            _ => self.map[".source"].clone(),
+        }
     }
 
     /// Returns the command that is specified in the template meta information.
@@ -142,3 +302,14 @@ impl TemplateMeta {
         self.map["command"].clone()
     }
 }
+
+/// Returns an error status listing the given snippet failures, or ok if there are none.
+fn snippet_failure_status(failures: Vec<String>) -> Status {
+    if failures.is_empty() {
+        return Status::ok();
+    }
+    Status::error(format!(
+        "Required snippets could not be applied:\n  {}",
+        failures.join("\n  ")
+    ))
+}
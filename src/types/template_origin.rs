@@ -0,0 +1,8 @@
+#[derive(Clone, Debug, PartialEq)]
+/// Where a template is stored.
+pub(crate) enum TemplateOrigin {
+    /// The `.templates` directory of the project.
+    Project,
+    /// The global template library of the user (e.g. `~/.local/share/templify/templates`).
+    Global,
+}
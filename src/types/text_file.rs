@@ -0,0 +1,54 @@
+/// The byte order mark that some editors put at the start of UTF-8 files.
+const BOM: char = '\u{feff}';
+
+/// The lines of a text file together with its formatting, so it can be written back unchanged.
+pub(crate) struct TextFile {
+    pub lines: Vec<String>,
+    line_ending: &'static str,
+    final_newline: bool,
+    bom: bool,
+}
+
+impl TextFile {
+    /// Split the given file content into lines and detect its formatting.
+    pub fn parse(content: &str) -> TextFile {
+        let bom = content.starts_with(BOM);
+        let content = content.strip_prefix(BOM).unwrap_or(content);
+
+        // the first line break decides the line ending style of the file
+        let line_ending = match content.find('\n') {
+            Some(i) if content[..i].ends_with('\r') => "\r\n",
+            _ => "\n",
+        };
+
+        TextFile {
+            lines: content.lines().map(|l| l.to_string()).collect(),
+            line_ending,
+            final_newline: content.ends_with('\n'),
+            bom,
+        }
+    }
+
+    /// Read and parse the file at the given path.
+    pub fn read(path: &str) -> std::io::Result<TextFile> {
+        std::fs::read_to_string(path).map(|content| TextFile::parse(&content))
+    }
+
+    /// Join the lines with the original line ending, final newline and byte order mark.
+    pub fn to_content(&self) -> String {
+        let mut content = String::new();
+        if self.bom {
+            content.push(BOM);
+        }
+        content.push_str(&self.lines.join(self.line_ending));
+        if self.final_newline && !self.lines.is_empty() {
+            content.push_str(self.line_ending);
+        }
+        content
+    }
+
+    /// Write the file to the given path.
+    pub fn write(&self, path: &str) -> std::io::Result<()> {
+        std::fs::write(path, self.to_content())
+    }
+}
@@ -0,0 +1,79 @@
+use crate::types::config::Config;
+use crate::types::status::Status;
+use chrono::{DateTime, FixedOffset, Local, TimeZone, Utc};
+use std::sync::Mutex;
+
+/// The time that was set with the `--now` global flag.
+static NOW_OVERRIDE: Mutex<Option<DateTime<FixedOffset>>> = Mutex::new(None);
+
+/// The time of the current run and if it was given (`--now` or `SOURCE_DATE_EPOCH`) instead of
+/// read from the system clock. It is read once at the start of the run.
+static RUN_TIME: Mutex<Option<(DateTime<FixedOffset>, bool)>> = Mutex::new(None);
+
+/// The time of the current run in the configured time zone. It is converted on first use,
+/// because the project config is only found after the command changed to the project root.
+static ZONED_RUN_TIME: Mutex<Option<DateTime<FixedOffset>>> = Mutex::new(None);
+
+/// Override the clock with the given RFC3339 time (e.g. `2024-01-31T12:00:00+01:00`).
+pub fn set_now(rfc3339: &str) -> Status {
+    match DateTime::parse_from_rfc3339(rfc3339) {
+        Ok(time) => {
+            *NOW_OVERRIDE.lock().unwrap() = Some(time);
+            Status::ok()
+        }
+        Err(e) => Status::error(format!("Invalid RFC3339 time {}: {}", rfc3339, e)),
+    }
+}
+
+/// Read the clock for a new run.
+pub fn start_run() {
+    *RUN_TIME.lock().unwrap() = Some(read_clock());
+    *ZONED_RUN_TIME.lock().unwrap() = None;
+}
+
+/// Read the clock. Uses the `--now` flag, `SOURCE_DATE_EPOCH` or the system time.
+/// Returns the time and if it was given instead of read from the system clock.
+fn read_clock() -> (DateTime<FixedOffset>, bool) {
+    match NOW_OVERRIDE.lock().unwrap().or_else(get_source_date_epoch) {
+        Some(time) => (time, true),
+        None => (Utc::now().fixed_offset(), false),
+    }
+}
+
+/// Returns the time of the current run in the configured time zone.
+pub fn now() -> DateTime<FixedOffset> {
+    let mut zoned_run_time = ZONED_RUN_TIME.lock().unwrap();
+    if let Some(time) = *zoned_run_time {
+        return time;
+    }
+
+    let (run_time, given) = *RUN_TIME.lock().unwrap().get_or_insert_with(read_clock);
+    let time = to_configured_timezone(run_time, given);
+    *zoned_run_time = Some(time);
+    time
+}
+
+/// Parse the `SOURCE_DATE_EPOCH` environment variable (see https://reproducible-builds.org).
+fn get_source_date_epoch() -> Option<DateTime<FixedOffset>> {
+    let epoch = std::env::var("SOURCE_DATE_EPOCH").ok()?;
+    let seconds = epoch.trim().parse::<i64>().ok()?;
+    Utc.timestamp_opt(seconds, 0)
+        .single()
+        .map(|time| time.fixed_offset())
+}
+
+/// Convert the given time to the time zone from the config (`timezone: utc|local`). Without a
+/// configured time zone a given time (`--now` or `SOURCE_DATE_EPOCH`) keeps its offset, so the
+/// output does not depend on the machine, and the system time is converted to the local time.
+fn to_configured_timezone(time: DateTime<FixedOffset>, given: bool) -> DateTime<FixedOffset> {
+    let timezone = Config::project()
+        .get("timezone")
+        .or(Config::user().get("timezone"))
+        .map(|timezone| timezone.to_lowercase());
+
+    match timezone.as_deref() {
+        Some("utc") => time.with_timezone(&Utc).fixed_offset(),
+        None if given => time,
+        _ => time.with_timezone(&Local).fixed_offset(),
+    }
+}
@@ -1,4 +1,19 @@
-use crate::{placeholder_storage::get_all_placeholders, types::template_meta::TemplateMeta};
+use crate::{
+    placeholder_storage::{
+        get_all_param_placeholders, get_placeholder_value, keep_acronyms, log_placeholder_errors,
+        take_placeholder_errors,
+    },
+    types::{generate_types::FileContext, template_meta::TemplateMeta},
+    utils::functions::relative_path,
+};
+use regex::{Captures, Regex};
+use std::sync::OnceLock;
+
+/// The regex for placeholders without a parameter (e.g. `$$name$$` or `$$name.kebab$$`).
+static SIMPLE_PLACEHOLDER_REGEX: OnceLock<Regex> = OnceLock::new();
+
+/// The regex for placeholders with a parameter (e.g. `$$date:%Y$$`).
+static PARAM_PLACEHOLDER_REGEX: OnceLock<Regex> = OnceLock::new();
 
 /// This function is used to handle the placeholders in a template string.
 pub(crate) fn handle_placeholders(
@@ -6,78 +21,161 @@ pub(crate) fn handle_placeholders(
     name: &str,
     meta: TemplateMeta,
 ) -> String {
-    let mut s = content_to_parse.to_string();
-
-    s = s.replace("$$name$$", name);
-    s = handle_case_conversion("name", name, s.as_str());
-
-    s = handle_static_placeholders(s.as_str());
-    s = handle_variable_placeholders(s.as_str(), meta);
+    let content = render_placeholders(content_to_parse, name, &meta, None);
+    log_placeholder_errors(take_placeholder_errors());
+    content
+}
 
-    s
+/// This function is used to handle the placeholders in the content of a generated file.
+/// In addition to the default placeholders the location placeholders of the file are replaced.
+pub(crate) fn handle_placeholders_for_file(
+    content_to_parse: &str,
+    name: &str,
+    meta: TemplateMeta,
+    context: &FileContext,
+) -> String {
+    let (content, errors) = render_file_placeholders(content_to_parse, name, meta, context);
+    log_placeholder_errors(errors);
+    content
 }
 
-/// Handle and replace the static placeholders in a template string.
-fn handle_static_placeholders(content_to_parse: &str) -> String {
-    let mut s = content_to_parse.to_string();
-    for ph in get_all_placeholders() {
-        s = s.replace(
-            format!("$${}$$", ph.name).as_str(),
-            (ph.get_value)().as_str(),
-        );
-        s = handle_case_conversion(ph.name.as_str(), (ph.get_value)().as_str(), s.as_str());
-    }
-    s
+/// Like `handle_placeholders_for_file`, but the errors are returned instead of logged.
+/// This is used on worker threads, the errors have to be logged on the main thread.
+pub(crate) fn render_file_placeholders(
+    content_to_parse: &str,
+    name: &str,
+    meta: TemplateMeta,
+    context: &FileContext,
+) -> (String, Vec<String>) {
+    let content = render_placeholders(content_to_parse, name, &meta, Some(context));
+    (content, take_placeholder_errors())
 }
 
-/// This function is used to handle the variable placeholders in a template string.
-fn handle_variable_placeholders(content_to_parse: &str, meta: TemplateMeta) -> String {
-    let mut s = content_to_parse.to_string();
-    for (_, p) in meta.var_placeholder_collection.placeholders {
-        s = s.replace(format!("$${}$$", p.name).as_str(), p.value.as_str());
-        s = handle_case_conversion(p.name.as_str(), p.value.as_str(), s.as_str());
+/// Replace all placeholders in a single scan of the content.
+///
+/// Placeholders without a parameter (`$$name$$`, `$$name.kebab$$`) are replaced first, so
+/// they can be used inside the parameter of a placeholder (`$$relpath:src/$$name$$$$`).
+fn render_placeholders(
+    content_to_parse: &str,
+    name: &str,
+    meta: &TemplateMeta,
+    context: Option<&FileContext>,
+) -> String {
+    if !content_to_parse.contains("$$") {
+        return content_to_parse.to_string();
     }
-    s
-}
 
-/// This function is used to handle the case conversion for placeholders.
-fn handle_case_conversion(placeholder_name: &str, value: &str, s: &str) -> String {
-    let mut s = s.to_string();
-    let tokens = tokenize_string(value);
+    let keep_acronyms = OnceLock::new();
+
+    let simple_regex =
+        SIMPLE_PLACEHOLDER_REGEX.get_or_init(|| Regex::new(r"\$\$([a-zA-Z0-9_.-]+)\$\$").unwrap());
+    let s = simple_regex.replace_all(content_to_parse, |captures: &Captures| {
+        let placeholder = &captures[1];
+        if let Some(value) = get_simple_value(placeholder, name, meta, context) {
+            return value;
+        }
+
+        // case conversion (e.g. `name.kebab`)
+        if let Some((base, style)) = placeholder.rsplit_once('.') {
+            if let Some(value) = get_simple_value(base, name, meta, context) {
+                let keep_acronyms = *keep_acronyms.get_or_init(self::keep_acronyms);
+                if let Some(converted) = convert_case(&value, style, keep_acronyms) {
+                    return converted;
+                }
+            }
+        }
+        captures[0].to_string()
+    });
 
-    let lower = to_total_lower_case(tokens.clone());
-    s = s.replace(format!("$${}.lower$$", placeholder_name).as_str(), &lower);
-    s = s.replace(format!("$${}.l$$", placeholder_name).as_str(), &lower);
+    if !s.contains(':') {
+        return s.to_string();
+    }
 
-    let upper = to_total_upper_case(tokens.clone());
-    s = s.replace(format!("$${}.upper$$", placeholder_name).as_str(), &upper);
-    s = s.replace(format!("$${}.u$$", placeholder_name).as_str(), &upper);
+    let param_regex = PARAM_PLACEHOLDER_REGEX
+        .get_or_init(|| Regex::new(r"\$\$([a-zA-Z][a-zA-Z0-9_-]*):([^\n$]*)\$\$").unwrap());
+    let param_placeholders = get_all_param_placeholders();
+    param_regex
+        .replace_all(&s, |captures: &Captures| {
+            if &captures[1] == "relpath" {
+                if let Some(context) = context {
+                    return relative_path(&context.get_output_dir(), captures[2].trim());
+                }
+            }
+            match param_placeholders.iter().find(|ph| ph.name == captures[1]) {
+                Some(ph) => (ph.get_value)(&captures[2]),
+                None => captures[0].to_string(),
+            }
+        })
+        .to_string()
+}
 
-    let camel = to_camel_case(tokens.clone());
-    s = s.replace(format!("$${}.camel$$", placeholder_name).as_str(), &camel);
-    s = s.replace(format!("$${}.c$$", placeholder_name).as_str(), &camel);
+/// Returns the value of a placeholder without a parameter or case conversion.
+fn get_simple_value(
+    placeholder: &str,
+    name: &str,
+    meta: &TemplateMeta,
+    context: Option<&FileContext>,
+) -> Option<String> {
+    match placeholder {
+        "name" => return Some(name.to_string()),
+        "template-name" => {
+            // the namespace of the template is not part of the name (e.g. react/component)
+            let template_name = meta.get_template_name();
+            let template_name = template_name.rsplit('/').next().unwrap_or_default();
+            return Some(template_name.to_string());
+        }
+        "output-dir" if context.is_some() => return Some(context?.get_output_dir()),
+        "file-path" if context.is_some() => return Some(context?.file_path.clone()),
+        _ => {}
+    }
 
-    let snake = to_snake_case(tokens.clone());
-    s = s.replace(format!("$${}.snake$$", placeholder_name).as_str(), &snake);
-    s = s.replace(format!("$${}.s$$", placeholder_name).as_str(), &snake);
+    if let Some(value) = get_placeholder_value(placeholder) {
+        return Some(value);
+    }
 
-    let kebab = to_kebab_case(tokens.clone());
-    s = s.replace(format!("$${}.kebab$$", placeholder_name).as_str(), &kebab);
-    s = s.replace(format!("$${}.k$$", placeholder_name).as_str(), &kebab);
+    meta.var_placeholder_collection
+        .placeholders
+        .get(placeholder)
+        .map(|p| p.value.clone())
+}
 
-    let pascal = to_pascal_case(tokens.clone());
-    s = s.replace(format!("$${}.pascal$$", placeholder_name).as_str(), &pascal);
-    s = s.replace(format!("$${}.p$$", placeholder_name).as_str(), &pascal);
+/// A case conversion with its long name, its optional short name and the conversion function.
+type CaseConversion = (
+    &'static str,
+    Option<&'static str>,
+    fn(Vec<String>) -> String,
+);
 
-    let macro_s = to_macro_case(tokens.clone());
-    s = s.replace(format!("$${}.macro$$", placeholder_name).as_str(), &macro_s);
-    s = s.replace(format!("$${}.m$$", placeholder_name).as_str(), &macro_s);
+/// Returns all supported case conversions.
+fn get_case_conversions() -> Vec<CaseConversion> {
+    vec![
+        ("lower", Some("l"), to_total_lower_case),
+        ("upper", Some("u"), to_total_upper_case),
+        ("camel", Some("c"), to_camel_case),
+        ("snake", Some("s"), to_snake_case),
+        ("kebab", Some("k"), to_kebab_case),
+        ("pascal", Some("p"), to_pascal_case),
+        ("macro", Some("m"), to_macro_case),
+        ("train", Some("t"), to_train_case),
+        ("dot", None, to_dot_case),
+        ("path", None, to_path_case),
+        ("title", None, to_title_case),
+        ("sentence", None, to_sentence_case),
+        ("flat", None, to_flat_case),
+        ("cobol", None, to_cobol_case),
+    ]
+}
 
-    let train = to_train_case(tokens.clone());
-    s = s.replace(format!("$${}.train$$", placeholder_name).as_str(), &train);
-    s = s.replace(format!("$${}.t$$", placeholder_name).as_str(), &train);
+/// Convert the given value to the case style with the given (long or short) name.
+fn convert_case(value: &str, style: &str, keep_acronyms: bool) -> Option<String> {
+    let (_, _, convert) = get_case_conversions()
+        .into_iter()
+        .find(|(name, short_name, _)| *name == style || *short_name == Some(style))?;
 
-    s
+    Some(convert(normalize_tokens(
+        tokenize_string(value),
+        keep_acronyms,
+    )))
 }
 
 /// Transforms the given tokens to a lower case string.
@@ -92,16 +190,11 @@ fn to_total_upper_case(tokens: Vec<String>) -> String {
 
 /// Transforms the given tokens to a train case string.
 fn to_train_case(tokens: Vec<String>) -> String {
-    let mut result = String::new();
-    for (i, token) in tokens.iter().enumerate() {
-        let newtoken = format!("{}{}", &token[0..1].to_uppercase(), &token[1..]);
-        if i == 0 {
-            result.push_str(&newtoken);
-        } else {
-            result.push_str(&format!("-{}", newtoken));
-        }
-    }
-    result
+    tokens
+        .iter()
+        .map(|token| capitalize(token))
+        .collect::<Vec<String>>()
+        .join("-")
 }
 
 /// Transforms the given tokens to a macro case string.
@@ -111,22 +204,17 @@ fn to_macro_case(tokens: Vec<String>) -> String {
 
 /// Transforms the given tokens to a pascal case string.
 fn to_pascal_case(tokens: Vec<String>) -> String {
-    let mut result = String::new();
-    for token in tokens {
-        let newtoken = format!("{}{}", &token[0..1].to_uppercase(), &token[1..]);
-        result.push_str(&newtoken);
-    }
-    result
+    tokens.iter().map(|token| capitalize(token)).collect()
 }
 
 /// Transforms the given tokens to a kebab case string.
 fn to_kebab_case(tokens: Vec<String>) -> String {
-    tokens.join("-")
+    tokens.join("-").to_lowercase()
 }
 
 /// Transforms the given tokens to a snake case string.
 fn to_snake_case(tokens: Vec<String>) -> String {
-    tokens.join("_")
+    tokens.join("_").to_lowercase()
 }
 
 /// Transforms the given tokens to a camel case string.
@@ -134,36 +222,123 @@ fn to_camel_case(tokens: Vec<String>) -> String {
     let mut result = String::new();
     for (i, token) in tokens.iter().enumerate() {
         if i == 0 {
-            result.push_str(token);
+            result.push_str(&token.to_lowercase());
         } else {
-            let newtoken = format!("{}{}", &token[0..1].to_uppercase(), &token[1..]);
-            result.push_str(&newtoken);
+            result.push_str(&capitalize(token));
         }
     }
     result
 }
 
-/// Parses the given string into tokens.
-fn tokenize_string(input: &str) -> Vec<String> {
-    let mut result = input.replace(['-', '_'], " ").to_string();
-    result = result.replace('_', " ");
-
-    // replace all uppercase letters with lowercase and a space before
-    result = result
-        .chars()
-        .enumerate()
-        .map(|(i, c)| {
-            if c.is_uppercase() && i > 0 {
-                format!(" {}", c.to_lowercase())
+/// Transforms the given tokens to a dot case string.
+fn to_dot_case(tokens: Vec<String>) -> String {
+    tokens.join(".").to_lowercase()
+}
+
+/// Transforms the given tokens to a path case string.
+fn to_path_case(tokens: Vec<String>) -> String {
+    tokens.join("/").to_lowercase()
+}
+
+/// Transforms the given tokens to a title case string.
+fn to_title_case(tokens: Vec<String>) -> String {
+    tokens
+        .iter()
+        .map(|token| capitalize(token))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// Transforms the given tokens to a sentence case string.
+fn to_sentence_case(tokens: Vec<String>) -> String {
+    let mut result = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        if i == 0 {
+            result.push(capitalize(token));
+        } else {
+            result.push(token.clone());
+        }
+    }
+    result.join(" ")
+}
+
+/// Transforms the given tokens to a flat case string.
+fn to_flat_case(tokens: Vec<String>) -> String {
+    tokens.join("").to_lowercase()
+}
+
+/// Transforms the given tokens to a cobol case string.
+fn to_cobol_case(tokens: Vec<String>) -> String {
+    tokens.join("-").to_uppercase()
+}
+
+/// Uppercases the first character of the given token (unicode-safe).
+fn capitalize(token: &str) -> String {
+    let mut chars = token.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Returns if the given token is an acronym (e.g. `HTTP`).
+fn is_acronym(token: &str) -> bool {
+    token.chars().filter(|c| c.is_alphabetic()).count() > 1
+        && token
+            .chars()
+            .filter(|c| c.is_alphabetic())
+            .all(|c| c.is_uppercase())
+}
+
+/// Lowercases the given tokens. Acronyms are kept if `keep_acronyms` is set.
+fn normalize_tokens(tokens: Vec<String>, keep_acronyms: bool) -> Vec<String> {
+    tokens
+        .into_iter()
+        .map(|token| {
+            if keep_acronyms && is_acronym(&token) {
+                token
             } else {
-                c.to_string()
+                token.to_lowercase()
             }
         })
-        .collect::<Vec<String>>()
-        .join("");
-    return result
-        .to_lowercase()
-        .split_whitespace()
-        .map(|s| s.to_string())
-        .collect();
+        .collect()
+}
+
+/// Parses the given string into tokens. The original casing of the tokens is kept.
+///
+/// Words are split on separators (whitespace, `-`, `_`, `.`, `/`, `\`) and on case changes.
+/// Acronym runs are kept together (`HTTPServer` -> `HTTP`, `Server`) and digits stay
+/// with the preceding characters (`v2Api` -> `v2`, `Api`).
+fn tokenize_string(input: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut current: Vec<char> = Vec::new();
+
+    let chars = input.chars().collect::<Vec<char>>();
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_whitespace() || matches!(c, '-' | '_' | '.' | '/' | '\\') {
+            if !current.is_empty() {
+                tokens.push(current.drain(..).collect());
+            }
+            continue;
+        }
+
+        if let Some(&prev) = current.last() {
+            let next = chars.get(i + 1);
+            // `myFirst` or `v2Api` -> split before the upper case character
+            let lower_to_upper = c.is_uppercase() && !prev.is_uppercase();
+            // `HTTPServer` -> split before the last upper case character of the acronym
+            let acronym_end =
+                c.is_uppercase() && prev.is_uppercase() && next.is_some_and(|n| n.is_lowercase());
+
+            if lower_to_upper || acronym_end {
+                tokens.push(current.drain(..).collect());
+            }
+        }
+        current.push(c);
+    }
+
+    if !current.is_empty() {
+        tokens.push(current.into_iter().collect());
+    }
+    tokens
 }
@@ -0,0 +1,119 @@
+use crate::placeholder_storage::is_command_confirmed;
+use crate::types::config::{yaml_to_string, Config};
+use crate::utils::functions::{execute_streamed_command, normalize_path, shell_quote};
+use crate::{error, log};
+use yaml_rust::Yaml;
+
+/// A formatter from the project config that is run on the generated files matching its pattern.
+struct Formatter {
+    pattern: glob::Pattern,
+    command: String,
+}
+
+impl Formatter {
+    /// Returns if the given file matches the pattern of the formatter.
+    /// Patterns without a `/` are matched against the file name only.
+    fn matches(&self, file: &str) -> bool {
+        if self.pattern.as_str().contains('/') {
+            return self.pattern.matches(file);
+        }
+        let file_name = file.rsplit('/').next().unwrap_or(file);
+        self.pattern.matches(file_name)
+    }
+
+    /// Returns the command for the given files. `{files}` is replaced with the quoted file
+    /// paths, if the command does not contain it the files are appended.
+    fn get_command(&self, files: &[&String]) -> String {
+        let files = files
+            .iter()
+            .map(|file| shell_quote(file))
+            .collect::<Vec<String>>()
+            .join(" ");
+        if self.command.contains("{files}") {
+            self.command.replace("{files}", &files)
+        } else {
+            format!("{} {}", self.command, files)
+        }
+    }
+}
+
+/// Returns the `formatters` map of the project config (file glob and command).
+pub(crate) fn get_formatter_commands() -> Vec<(String, String)> {
+    let config = Config::project();
+    let hash = match config.get_yaml("formatters") {
+        Some(Yaml::Hash(hash)) => hash.clone(),
+        _ => return Vec::new(),
+    };
+
+    hash.iter()
+        .filter_map(
+            |(pattern, command)| match (yaml_to_string(pattern), yaml_to_string(command)) {
+                (Some(pattern), Some(command)) if !command.trim().is_empty() => {
+                    Some((pattern, command))
+                }
+                _ => None,
+            },
+        )
+        .collect()
+}
+
+/// Returns the formatters from the `formatters` map of the project config.
+fn get_formatters() -> Vec<Formatter> {
+    let mut formatters = Vec::new();
+    for (pattern, command) in get_formatter_commands() {
+        match glob::Pattern::new(&pattern) {
+            Ok(pattern) => formatters.push(Formatter { pattern, command }),
+            Err(e) => {
+                error!("Ignoring the formatter for {}: {}", pattern, e);
+            }
+        }
+    }
+    formatters
+}
+
+/// Run the configured formatters once on all of the given files that match their pattern.
+/// Failing formatters are reported but do not abort the generation.
+pub(crate) fn run_formatters(files: &[String]) {
+    let files = files
+        .iter()
+        .map(|file| normalize_path(file))
+        .filter(|file| std::path::Path::new(file).is_file())
+        .collect::<Vec<String>>();
+    if files.is_empty() {
+        return;
+    }
+
+    for formatter in get_formatters() {
+        let matching = files
+            .iter()
+            .filter(|file| formatter.matches(file))
+            .collect::<Vec<&String>>();
+        if matching.is_empty() {
+            continue;
+        }
+        // the project config is part of the repository, see confirm_project_commands
+        if !is_command_confirmed(&formatter.command) {
+            error!(
+                "Skipping the formatter for {}, its command was not confirmed.",
+                formatter.pattern.as_str()
+            );
+            continue;
+        }
+
+        let command = formatter.get_command(&matching);
+        log!("Running formatter: {}", command);
+        match execute_streamed_command(command, ".", &[], None) {
+            Ok(0) => {}
+            Ok(code) => {
+                error!(
+                    "Formatter for {} failed with exit code {}.",
+                    formatter.pattern.as_str(),
+                    code
+                );
+            }
+            Err(e) => {
+                error!("Formatter for {} failed: {}", formatter.pattern.as_str(), e);
+            }
+        }
+    }
+}
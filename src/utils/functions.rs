@@ -1,17 +1,113 @@
+use crate::types::config::{Config, PROJECT_CONFIG_FILE};
+use crate::utils::clock;
 use crate::{logger, types::status::Status};
 use chrono::Datelike;
+use regex::Regex;
 use serde_json::Value;
+use std::io::{BufRead, BufReader, IsTerminal, Read};
 use std::io::{Error, ErrorKind};
-use std::process::Command;
-use std::{io::Write, path::Path};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
 
 /// Check if templify is initialized in the current project
+/// If the `.templates` directory is found in a parent directory, the current directory is changed
+/// to this project root, so that all paths are resolved against it.
 pub(crate) fn check_if_templify_initialized() -> Status {
-    if !Path::new(".templates").exists() {
-        let command_name = unsafe { crate::env::BASE_COMMAND_NAME.clone() };
-        return Status::error(format!("templify is not initialized in this project.\nRun `{} init` to initialize templify in your project.",command_name));
+    if Path::new(&get_templates_dir()).is_dir() {
+        return Status::ok();
     }
-    Status::ok()
+
+    let invocation_dir = std::env::current_dir().ok();
+    let root = invocation_dir
+        .as_ref()
+        .and_then(|dir| find_project_root(dir));
+    match (invocation_dir, root) {
+        (Some(invocation_dir), Some(root)) => {
+            if let Err(e) = std::env::set_current_dir(&root) {
+                return Status::error(format!(
+                    "Failed to change to the project root {}: {}",
+                    root.display(),
+                    e
+                ));
+            }
+            crate::env::set_invocation_dir(invocation_dir);
+            Status::ok()
+        }
+        _ => {
+            let command_name = unsafe { crate::env::BASE_COMMAND_NAME.clone() };
+            Status::error(format!("templify is not initialized in this project.\nRun `{} init` to initialize templify in your project.",command_name))
+        }
+    }
+}
+
+/// Find the nearest directory (starting with the given one) that contains a templates directory.
+pub(crate) fn find_project_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|dir| dir.join(get_templates_dir_in(dir)).is_dir())
+        .map(|dir| dir.to_path_buf())
+}
+
+/// Returns the templates directory of the project (relative to the project root).
+pub(crate) fn get_templates_dir() -> String {
+    get_templates_dir_in(Path::new("."))
+}
+
+/// Returns the templates directory of the project in the given directory. It is taken from the
+/// `--templates-dir` global flag, the `TPY_TEMPLATES_DIR` environment variable or the
+/// `templates-dir` key of the project config (in this order) and defaults to `.templates`.
+pub(crate) fn get_templates_dir_in(dir: &Path) -> String {
+    if let Some(templates_dir) = crate::env::get_templates_dir() {
+        return templates_dir;
+    }
+    if let Ok(templates_dir) = std::env::var("TPY_TEMPLATES_DIR") {
+        if !templates_dir.trim().is_empty() {
+            return normalize_path(templates_dir.trim());
+        }
+    }
+    let config_path = dir.join(PROJECT_CONFIG_FILE);
+    match Config::load(config_path.to_string_lossy().to_string()).get("templates-dir") {
+        Some(templates_dir) if !templates_dir.trim().is_empty() => {
+            normalize_path(templates_dir.trim())
+        }
+        _ => ".templates".to_string(),
+    }
+}
+
+/// Returns the directory templify was started in relative to the project root (`.` if it is the root).
+pub(crate) fn get_invocation_subdir() -> String {
+    let invocation_dir = match crate::env::get_invocation_dir() {
+        Some(dir) => dir,
+        None => return ".".to_string(),
+    };
+    let root = std::env::current_dir().unwrap_or_default();
+    match invocation_dir.strip_prefix(&root) {
+        Ok(subdir) => normalize_path(&subdir.to_string_lossy()),
+        Err(_) => ".".to_string(),
+    }
+}
+
+/// Resolve a path given on the command line against the directory templify was started in.
+/// The result is relative to the project root (or absolute if the given path is absolute).
+pub(crate) fn resolve_invocation_path(path: &str) -> String {
+    if Path::new(path).is_absolute() {
+        return path.to_string();
+    }
+    normalize_path(&format!("{}/{}", get_invocation_subdir(), path))
+}
+
+/// Returns if the user can be asked for a confirmation. This is the case if stdin is a terminal
+/// or `TPY_INTERACTIVE` is set (e.g. to answer the prompts through a pipe).
+pub(crate) fn is_interactive() -> bool {
+    let forced = std::env::var("TPY_INTERACTIVE")
+        .map(|value| matches!(value.trim().to_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false);
+    forced || std::io::stdin().is_terminal()
 }
 
 /// Check if a internet connection is available
@@ -27,8 +123,46 @@ pub fn check_internet_connection() -> bool {
     false
 }
 
-/// Get the git user name from the git configuration
+/// Get the path of the user-level config file (respects `XDG_CONFIG_HOME`)
+pub fn get_user_config_path() -> String {
+    format!("{}/config.yml", get_user_config_dir())
+}
+
+/// Get the directory of the user-level templify files (`$XDG_CONFIG_HOME/templify` or `~/.config/templify`)
+pub fn get_user_config_dir() -> String {
+    let config_dir = match std::env::var("XDG_CONFIG_HOME") {
+        Ok(dir) if !dir.trim().is_empty() => dir,
+        _ => {
+            let home = std::env::var("HOME")
+                .or_else(|_| std::env::var("USERPROFILE"))
+                .unwrap_or_else(|_| ".".to_string());
+            format!("{}/.config", home)
+        }
+    };
+    format!("{}/templify", config_dir)
+}
+
+/// Get the directory of the global template library
+/// (`$XDG_DATA_HOME/templify/templates`, defaults to `~/.local/share/templify/templates`).
+pub fn get_global_templates_dir() -> String {
+    let data_dir = match std::env::var("XDG_DATA_HOME") {
+        Ok(dir) if !dir.trim().is_empty() => dir,
+        _ => {
+            let home = std::env::var("HOME")
+                .or_else(|_| std::env::var("USERPROFILE"))
+                .unwrap_or_else(|_| ".".to_string());
+            format!("{}/.local/share", home)
+        }
+    };
+    format!("{}/templify/templates", data_dir)
+}
+
+/// Get the author name from the user config or the git configuration
 pub fn get_git_name() -> String {
+    if let Some(name) = Config::user().get("author.name") {
+        return name;
+    }
+
     let output = std::process::Command::new("git")
         .arg("config")
         .arg("user.name")
@@ -45,9 +179,61 @@ pub fn get_git_name() -> String {
     name
 }
 
+/// Get the git user email from the git configuration
+pub fn get_git_email() -> String {
+    let output = std::process::Command::new("git")
+        .arg("config")
+        .arg("user.email")
+        .output();
+    if output.is_err() {
+        return "unknown".to_string();
+    }
+    let output = output.unwrap();
+    let output = String::from_utf8_lossy(&output.stdout);
+    let mut email = output.trim().to_string();
+    if email.is_empty() {
+        email = "unknown".to_string();
+    }
+    email
+}
+
+/// Get the author email from the user config or the git configuration
+pub fn get_author_email() -> String {
+    if let Some(email) = Config::user().get("author.email") {
+        return email;
+    }
+    get_git_email()
+}
+
+/// Get the project name from the manifest (Cargo.toml or package.json) or the directory name
+pub fn get_project_name() -> String {
+    if let Ok(content) = std::fs::read_to_string("Cargo.toml") {
+        let name_regex = Regex::new(r#"(?m)^\s*name\s*=\s*"([^"]+)""#).unwrap();
+        if let Some(package_section) = content.split("[package]").nth(1) {
+            let package_section = package_section.split("\n[").next().unwrap_or("");
+            if let Some(captures) = name_regex.captures(package_section) {
+                return captures[1].to_string();
+            }
+        }
+    }
+
+    if let Ok(content) = std::fs::read_to_string("package.json") {
+        if let Ok(json) = serde_json::from_str::<Value>(&content) {
+            if let Some(name) = json["name"].as_str() {
+                return name.to_string();
+            }
+        }
+    }
+
+    std::env::current_dir()
+        .ok()
+        .and_then(|dir| dir.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or("unknown".to_string())
+}
+
 /// Returns the current month as a string.
 pub fn get_month_string() -> String {
-    let month = chrono::Local::now().month();
+    let month = clock::now().month();
     match month {
         1 => "Jan",
         2 => "Feb",
@@ -76,6 +262,20 @@ pub(crate) fn handle_dev_mode() -> Status {
     Status::ok()
 }
 
+/// Override the clock that is used for the date placeholders
+pub(crate) fn handle_now(time: String) -> Status {
+    clock::set_now(&time)
+}
+
+/// Use the given templates directory instead of the configured one
+pub(crate) fn handle_templates_dir(dir: String) -> Status {
+    if dir.trim().is_empty() {
+        return Status::error("The templates directory must not be empty.".to_string());
+    }
+    crate::env::set_templates_dir(normalize_path(dir.trim()));
+    Status::ok()
+}
+
 /// Prepare the quiet mode
 pub(crate) fn handle_quiet_mode() -> Status {
     logger::remove_logger_entity("stdout");
@@ -94,6 +294,10 @@ pub(crate) fn handle_log_file(file: String) -> Status {
         }
     }
 
+    // use the absolute path because the current directory may change to the project root
+    let file = std::fs::canonicalize(&file)
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or(file);
     let file_path = file.clone();
 
     let file_logger: Box<dyn Fn(&str)> = Box::new(move |message: &str| {
@@ -126,15 +330,223 @@ pub(crate) fn handle_log_file(file: String) -> Status {
     Status::ok()
 }
 
+#[cfg(not(windows))]
+/// Quote the given argument for `sh` with single quotes, so that no character of it
+/// (e.g. `$`, `` ` `` or `"`) is interpreted by the shell.
+pub(crate) fn shell_quote(argument: &str) -> String {
+    format!("'{}'", argument.replace('\'', "'\\''"))
+}
+
+#[cfg(windows)]
+/// Quote the given argument for `cmd.exe` with double quotes (file names on Windows can not
+/// contain a `"`).
+pub(crate) fn shell_quote(argument: &str) -> String {
+    format!("\"{}\"", argument.replace('"', "\"\""))
+}
+
+/// Normalize a path lexically (resolves `.` and `..` and uses `/` as separator).
+pub fn normalize_path(path: &str) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split(['/', '\\']) {
+        match part {
+            "" | "." => continue,
+            ".." => {
+                if parts.is_empty() || parts.last() == Some(&"..") {
+                    parts.push("..");
+                } else {
+                    parts.pop();
+                }
+            }
+            _ => parts.push(part),
+        }
+    }
+    if path.starts_with('/') {
+        return format!("/{}", parts.join("/"));
+    }
+    if parts.is_empty() {
+        return ".".to_string();
+    }
+    parts.join("/")
+}
+
+/// Get the relative path from the directory `from_dir` to `to` (both relative to the project root).
+pub fn relative_path(from_dir: &str, to: &str) -> String {
+    let from_dir = normalize_path(from_dir);
+    let to = normalize_path(to);
+    let from_parts = from_dir
+        .split('/')
+        .filter(|p| *p != ".")
+        .collect::<Vec<&str>>();
+    let to_parts = to.split('/').filter(|p| *p != ".").collect::<Vec<&str>>();
+
+    let common = from_parts
+        .iter()
+        .zip(to_parts.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut result = vec![".."; from_parts.len() - common];
+    result.extend(&to_parts[common..]);
+
+    if result.is_empty() {
+        return ".".to_string();
+    }
+    let result = result.join("/");
+    if result.starts_with("..") {
+        return result;
+    }
+    format!("./{}", result)
+}
+
+/// Run the given function for all items on a pool of worker threads.
+/// The results are returned in the same order as the items.
+pub fn run_in_parallel<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let workers = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(items.len());
+    if workers <= 1 {
+        return items.iter().map(&f).collect();
+    }
+
+    let next_index = AtomicUsize::new(0);
+    let mut results = std::thread::scope(|scope| {
+        let handles = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut worker_results = Vec::new();
+                    loop {
+                        let i = next_index.fetch_add(1, Ordering::SeqCst);
+                        if i >= items.len() {
+                            break;
+                        }
+                        worker_results.push((i, f(&items[i])));
+                    }
+                    worker_results
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect::<Vec<(usize, R)>>()
+    });
+
+    results.sort_by_key(|(i, _)| *i);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Returns a diff of the given lines in a unified style (` `, `-` and `+` prefixes).
+/// Only changed lines and `context` unchanged lines around them are included; gaps are shown as `...`.
+pub fn diff_lines(old: &[String], new: &[String], context: usize) -> Vec<String> {
+    // the common prefix and suffix are unchanged, only the middle part needs to be compared
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut ops: Vec<(char, &str)> = old[..prefix].iter().map(|l| (' ', l.as_str())).collect();
+    ops.extend(diff_middle(
+        &old[prefix..old.len() - suffix],
+        &new[prefix..new.len() - suffix],
+    ));
+    ops.extend(old[old.len() - suffix..].iter().map(|l| (' ', l.as_str())));
+
+    let mut visible = vec![false; ops.len()];
+    for (i, (op, _)) in ops.iter().enumerate() {
+        if *op != ' ' {
+            let end = (i + context).min(ops.len() - 1);
+            visible[i.saturating_sub(context)..=end].fill(true);
+        }
+    }
+
+    let mut result = Vec::new();
+    let mut last_visible: Option<usize> = None;
+    for (i, (op, line)) in ops.iter().enumerate() {
+        if !visible[i] {
+            continue;
+        }
+        if last_visible.is_some_and(|last| i > last + 1) {
+            result.push("...".to_string());
+        }
+        result.push(format!("{} {}", op, line));
+        last_visible = Some(i);
+    }
+    result
+}
+
+/// Diff two blocks of lines using their longest common subsequence.
+/// Very large blocks are shown as completely removed and added.
+fn diff_middle<'a>(old: &'a [String], new: &'a [String]) -> Vec<(char, &'a str)> {
+    if old.len() * new.len() > 1_000_000 {
+        let removed = old.iter().map(|l| ('-', l.as_str()));
+        return removed
+            .chain(new.iter().map(|l| ('+', l.as_str())))
+            .collect();
+    }
+
+    // lcs[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0u32; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            ops.push((' ', old[i].as_str()));
+            i += 1;
+            j += 1;
+        } else if j < new.len() && (i == old.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            ops.push(('+', new[j].as_str()));
+            j += 1;
+        } else {
+            ops.push(('-', old[i].as_str()));
+            i += 1;
+        }
+    }
+    ops
+}
+
 /// Execute Command
 pub fn execute_user_command(command: String) -> Result<String, std::io::Error> {
+    execute_user_command_in(command, ".", &[])
+}
+
+/// Execute Command in the given directory with the given additional environment variables
+pub fn execute_user_command_in(
+    command: String,
+    dir: &str,
+    env: &[(String, String)],
+) -> Result<String, std::io::Error> {
     let (shell, flag) = if cfg!(target_os = "windows") {
         ("cmd.exe", "/C")
     } else {
         ("sh", "-c")
     };
 
-    let output = Command::new(shell).arg(flag).arg(command).output()?;
+    let output = Command::new(shell)
+        .arg(flag)
+        .arg(command)
+        .current_dir(dir)
+        .envs(env.iter().map(|(k, v)| (k, v)))
+        .output()?;
 
     if !output.status.success() {
         let exit_code = output.status.code().unwrap_or(-1);
@@ -148,6 +560,124 @@ pub fn execute_user_command(command: String) -> Result<String, std::io::Error> {
     String::from_utf8(output.stdout).map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))
 }
 
+/// Execute Command and stream its stdout and stderr line by line through the logger.
+/// The process is killed when the timeout is exceeded (error kind `TimedOut`).
+/// Returns the exit code of the command (-1 if it was terminated by a signal).
+pub fn execute_streamed_command(
+    command_line: String,
+    dir: &str,
+    env: &[(String, String)],
+    timeout: Option<Duration>,
+) -> Result<i32, std::io::Error> {
+    let (shell, flag) = if cfg!(target_os = "windows") {
+        ("cmd.exe", "/C")
+    } else {
+        ("sh", "-c")
+    };
+
+    let mut command = Command::new(shell);
+    command
+        .arg(flag)
+        .arg(command_line)
+        .current_dir(dir)
+        .envs(env.iter().map(|(k, v)| (k, v)))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    // run the command in its own process group, so that all of its processes can be killed
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+    let mut child = command.spawn()?;
+
+    // the logger is not thread-safe, so the reader threads send the lines to this thread
+    let (sender, receiver) = mpsc::channel::<(bool, String)>();
+    if let Some(stdout) = child.stdout.take() {
+        spawn_line_reader(stdout, false, sender.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        spawn_line_reader(stderr, true, sender);
+    }
+
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let timed_out = |child: &mut std::process::Child| {
+        kill_process_group(child);
+        let _ = child.wait();
+        Error::new(
+            ErrorKind::TimedOut,
+            format!(
+                "Command timed out after {} seconds",
+                timeout.unwrap_or_default().as_secs_f64()
+            ),
+        )
+    };
+
+    loop {
+        let message = match deadline {
+            Some(deadline) => {
+                let now = Instant::now();
+                if now >= deadline {
+                    return Err(timed_out(&mut child));
+                }
+                receiver.recv_timeout(deadline - now)
+            }
+            None => receiver
+                .recv()
+                .map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+        };
+        match message {
+            Ok((true, line)) => logger::write_error(&line),
+            Ok((false, line)) => logger::write_log(&line),
+            Err(mpsc::RecvTimeoutError::Timeout) => continue,
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    // the output is closed, wait for the process to exit
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status.code().unwrap_or(-1));
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Err(timed_out(&mut child));
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// Kill the given child process and all processes started by it.
+fn kill_process_group(child: &mut std::process::Child) {
+    #[cfg(unix)]
+    if let Ok(pid) = i32::try_from(child.id()) {
+        // the child is the leader of its process group, a negative pid targets the whole group
+        unsafe {
+            libc::kill(-pid, libc::SIGKILL);
+        }
+    }
+    let _ = child.kill();
+}
+
+/// Read the given output line by line on a new thread and send the lines to the receiver.
+fn spawn_line_reader<R: Read + Send + 'static>(
+    output: R,
+    is_error: bool,
+    sender: mpsc::Sender<(bool, String)>,
+) {
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(output);
+        let mut buffer = Vec::new();
+        while let Ok(read) = reader.read_until(b'\n', &mut buffer) {
+            if read == 0 {
+                break;
+            }
+            let line = String::from_utf8_lossy(&buffer);
+            let line = line.trim_end_matches(['\n', '\r']).to_string();
+            if sender.send((is_error, line)).is_err() {
+                break;
+            }
+            buffer.clear();
+        }
+    });
+}
+
 /// Extract the tree items from the github response
 ///
 /// # Arguments
@@ -0,0 +1,87 @@
+use crate::types::status::Status;
+use crate::utils::functions::get_user_config_dir;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+
+/// Returns the path of the file that stores the hashes of the trusted templates.
+fn get_trust_file_path() -> String {
+    format!("{}/trusted-templates", get_user_config_dir())
+}
+
+/// Returns the SHA-256 hash of the content of the given template directory.
+/// The hash changes whenever a file of the template (including hook scripts) changes.
+pub(crate) fn get_template_hash(template_dir: &str) -> String {
+    let mut files = Vec::new();
+    collect_files(Path::new(template_dir), &mut files);
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for file in files {
+        let relative = file
+            .strip_prefix(template_dir)
+            .unwrap_or(&file)
+            .to_string_lossy()
+            .replace('\\', "/");
+        hasher.update(relative.as_bytes());
+        hasher.update([0]);
+        hasher.update(std::fs::read(&file).unwrap_or_default());
+        hasher.update([0]);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Returns the SHA-256 hash of a command of the project config (e.g. of a custom placeholder).
+pub(crate) fn get_command_hash(command: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(b"command");
+    hasher.update([0]);
+    hasher.update(command.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Collect all files in the given directory recursively.
+fn collect_files(dir: &Path, files: &mut Vec<std::path::PathBuf>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// Returns if the template with the given hash has been trusted by the user.
+pub(crate) fn is_trusted(hash: &str) -> bool {
+    std::fs::read_to_string(get_trust_file_path())
+        .map(|content| {
+            content
+                .lines()
+                .any(|line| line.split_whitespace().next() == Some(hash))
+        })
+        .unwrap_or(false)
+}
+
+/// Remember the template with the given hash as trusted.
+pub(crate) fn trust(hash: &str, source: &str) -> Status {
+    let path = get_trust_file_path();
+    if let Some(parent) = Path::new(&path).parent() {
+        if std::fs::create_dir_all(parent).is_err() {
+            return Status::error(format!(
+                "Failed to create the config directory: {}",
+                parent.display()
+            ));
+        }
+    }
+
+    let mut content = std::fs::read_to_string(&path).unwrap_or_default();
+    content.push_str(&format!("{} {}\n", hash, source));
+    if let Err(e) = std::fs::write(&path, content) {
+        return Status::error(format!("Failed to write the file {}: {}", path, e));
+    }
+    Status::ok()
+}
@@ -1,5 +1,10 @@
+pub mod clock;
 pub mod formater;
+pub mod formatters;
 pub mod functions;
+pub mod hook_trust;
+pub mod output;
 pub mod rest;
+pub mod structured;
 pub mod template_handler;
 pub mod version_control;
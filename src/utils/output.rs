@@ -0,0 +1,96 @@
+use crate::log;
+use crate::types::generate_types::{FileToCreate, OutputTarget};
+use crate::types::status::Status;
+use crate::utils::clock;
+use crate::utils::functions::resolve_invocation_path;
+use std::io::Write;
+
+/// Write the rendered files of a template to the given output target instead of the filesystem.
+pub(crate) fn write_output(target: &OutputTarget, files: &[FileToCreate]) -> Status {
+    let result = match target {
+        OutputTarget::Stdout => write_stdout(files),
+        OutputTarget::Tar(archive) => write_tar(archive, files),
+        OutputTarget::Zip(archive) => write_zip(archive, files),
+    };
+    if let Err(e) = result {
+        return Status::error(format!("Failed to write the output: {}", e));
+    }
+
+    match target {
+        OutputTarget::Stdout => {
+            log!("Rendered {} file(s) to stdout.", files.len());
+        }
+        OutputTarget::Tar(archive) | OutputTarget::Zip(archive) => {
+            log!("Packaged {} file(s) into {}.", files.len(), archive);
+        }
+    }
+    Status::ok()
+}
+
+/// Print the files to stdout. A template with a single file is printed as it is,
+/// otherwise every file is preceded by a `==> path <==` header.
+fn write_stdout(files: &[FileToCreate]) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    if let [file] = files {
+        stdout.write_all(get_content(file))?;
+        return stdout.flush();
+    }
+
+    for (i, file) in files.iter().enumerate() {
+        if i > 0 {
+            writeln!(stdout)?;
+        }
+        writeln!(stdout, "==> {} <==", file.path)?;
+        let content = get_content(file);
+        stdout.write_all(content)?;
+        if !content.is_empty() && !content.ends_with(b"\n") {
+            writeln!(stdout)?;
+        }
+    }
+    stdout.flush()
+}
+
+/// Package the files into a tar archive.
+fn write_tar(archive: &str, files: &[FileToCreate]) -> std::io::Result<()> {
+    let mut builder = tar::Builder::new(create_archive_file(archive)?);
+    for file in files {
+        let content = get_content(file);
+        let mut header = tar::Header::new_gnu();
+        header.set_size(content.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(clock::now().timestamp().max(0) as u64);
+        header.set_cksum();
+        builder.append_data(&mut header, &file.path, content)?;
+    }
+    builder.into_inner()?.flush()
+}
+
+/// Package the files into a zip archive.
+fn write_zip(archive: &str, files: &[FileToCreate]) -> std::io::Result<()> {
+    let mut writer = zip::ZipWriter::new(create_archive_file(archive)?);
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated)
+        .unix_permissions(0o644);
+    for file in files {
+        writer.start_file(file.path.as_str(), options)?;
+        writer.write_all(get_content(file))?;
+    }
+    writer.finish()?.flush()
+}
+
+/// Create the archive file and its parent directories.
+/// The path is relative to the directory templify was started in.
+fn create_archive_file(archive: &str) -> std::io::Result<std::fs::File> {
+    let archive = resolve_invocation_path(archive);
+    if let Some(parent) = std::path::Path::new(&archive).parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    std::fs::File::create(archive)
+}
+
+/// Returns the rendered content of a file.
+fn get_content(file: &FileToCreate) -> &[u8] {
+    file.file_content.as_deref().unwrap_or("").as_bytes()
+}
@@ -0,0 +1,379 @@
+use serde_json::Value as JsonValue;
+use std::str::FromStr;
+use toml_edit::{DocumentMut, InlineTable, Item, TableLike};
+use yaml_rust::yaml::{Hash, Yaml};
+use yaml_rust::{YamlEmitter, YamlLoader};
+
+#[derive(Clone, Debug, PartialEq)]
+/// The format of a structured file a snippet value can be merged into.
+pub(crate) enum StructuredFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl FromStr for StructuredFormat {
+    type Err = String;
+
+    /// Parse the format from its name (`json`, `yaml`/`yml` or `toml`).
+    fn from_str(format: &str) -> Result<StructuredFormat, String> {
+        match format.trim().to_lowercase().as_str() {
+            "json" => Ok(StructuredFormat::Json),
+            "yaml" | "yml" => Ok(StructuredFormat::Yaml),
+            "toml" => Ok(StructuredFormat::Toml),
+            _ => Err(format!("Unknown format '{}'", format)),
+        }
+    }
+}
+
+impl StructuredFormat {
+    /// Detect the format from the extension of the given file name.
+    pub fn from_file_name(file_name: &str) -> Option<StructuredFormat> {
+        let (_, extension) = file_name.rsplit_once('.')?;
+        extension.parse().ok()
+    }
+}
+
+/// Split a key path (e.g. `dependencies.react`) into its keys.
+///
+/// Keys that contain a `.` can be quoted (`dependencies."@scope/pkg.name"` or `'a.b'`)
+/// or the `.` can be escaped with a backslash (`a\\.b`).
+pub(crate) fn parse_path(path: &str) -> Result<Vec<String>, String> {
+    let finish_key = |key: &str, quoted: bool| match quoted {
+        true => key.to_string(),
+        false => key.trim().to_string(),
+    };
+
+    let mut keys = Vec::new();
+    let mut key = String::new();
+    // only whitespace may follow a quoted key until the next `.`
+    let mut quoted = false;
+    let mut chars = path.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '.' => {
+                keys.push(finish_key(&key, quoted));
+                key.clear();
+                quoted = false;
+            }
+            _ if quoted => {
+                if !c.is_whitespace() {
+                    return Err(format!(
+                        "Unexpected '{}' after a quoted key in the path '{}'.",
+                        c, path
+                    ));
+                }
+            }
+            '\\' => match chars.next() {
+                Some(escaped) => key.push(escaped),
+                None => return Err(format!("The path '{}' ends with a backslash.", path)),
+            },
+            '"' | '\'' if key.trim().is_empty() => {
+                key.clear();
+                loop {
+                    match chars.next() {
+                        Some(quoted_char) if quoted_char == c => break,
+                        Some(quoted_char) => key.push(quoted_char),
+                        None => {
+                            return Err(format!("The path '{}' has an unterminated quote.", path))
+                        }
+                    }
+                }
+                quoted = true;
+            }
+            _ => key.push(c),
+        }
+    }
+    keys.push(finish_key(&key, quoted));
+    Ok(keys)
+}
+
+/// Merge the value into the document at the given key path.
+///
+/// Maps are merged recursively, all other values replace the existing value.
+/// Returns the new content, or `None` if the document already contains the value.
+pub(crate) fn merge_value(
+    content: &str,
+    format: &StructuredFormat,
+    path: &[String],
+    value: &Yaml,
+) -> Result<Option<String>, String> {
+    if path.is_empty() || path.iter().any(|key| key.is_empty()) {
+        return Err("The path must not be empty.".to_string());
+    }
+    match format {
+        StructuredFormat::Json => merge_json(content, path, value),
+        StructuredFormat::Yaml => merge_yaml(content, path, value),
+        StructuredFormat::Toml => merge_toml(content, path, value),
+    }
+}
+
+/// Merge the value into a JSON document. The indentation of the document is kept.
+fn merge_json(content: &str, path: &[String], value: &Yaml) -> Result<Option<String>, String> {
+    let mut document: JsonValue = if content.trim().is_empty() {
+        JsonValue::Object(serde_json::Map::new())
+    } else {
+        serde_json::from_str(content).map_err(|e| e.to_string())?
+    };
+    let original = document.clone();
+
+    let mut node = &mut document;
+    for key in &path[..path.len() - 1] {
+        let object = node
+            .as_object_mut()
+            .ok_or(format!("'{}' is not inside an object.", key))?;
+        node = object
+            .entry(key.clone())
+            .or_insert(JsonValue::Object(serde_json::Map::new()));
+    }
+    let object = node.as_object_mut().ok_or(format!(
+        "'{}' is not inside an object.",
+        path[path.len() - 1]
+    ))?;
+    let key = path[path.len() - 1].clone();
+    let new_value = yaml_to_json(value)?;
+    match object.get_mut(&key) {
+        Some(existing) => merge_json_value(existing, new_value),
+        None => {
+            object.insert(key, new_value);
+        }
+    }
+
+    if document == original {
+        return Ok(None);
+    }
+
+    // strings in JSON cannot contain line breaks, so the leading spaces are the indentation
+    let indent = detect_indent(content);
+    let output = serde_json::to_string_pretty(&document).map_err(|e| e.to_string())?;
+    let lines = output
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start_matches(' ');
+            let depth = (line.len() - trimmed.len()) / 2;
+            format!("{}{}", indent.repeat(depth), trimmed)
+        })
+        .collect::<Vec<String>>();
+    Ok(Some(lines.join("\n")))
+}
+
+/// Merge the new JSON value into the existing one.
+fn merge_json_value(existing: &mut JsonValue, new_value: JsonValue) {
+    match (existing, new_value) {
+        (JsonValue::Object(existing), JsonValue::Object(new_value)) => {
+            for (key, value) in new_value {
+                match existing.get_mut(&key) {
+                    Some(existing_value) => merge_json_value(existing_value, value),
+                    None => {
+                        existing.insert(key, value);
+                    }
+                }
+            }
+        }
+        (existing, new_value) => *existing = new_value,
+    }
+}
+
+/// Returns the indentation of the first indented line (default: two spaces).
+fn detect_indent(content: &str) -> String {
+    content
+        .lines()
+        .map(|line| {
+            line.chars()
+                .take_while(|c| *c == ' ' || *c == '\t')
+                .collect::<String>()
+        })
+        .find(|indent| !indent.is_empty())
+        .unwrap_or("  ".to_string())
+}
+
+/// Convert a YAML value to a JSON value.
+fn yaml_to_json(value: &Yaml) -> Result<JsonValue, String> {
+    Ok(match value {
+        Yaml::String(s) => JsonValue::String(s.clone()),
+        Yaml::Integer(i) => JsonValue::from(*i),
+        Yaml::Real(r) => JsonValue::from(r.parse::<f64>().map_err(|e| e.to_string())?),
+        Yaml::Boolean(b) => JsonValue::Bool(*b),
+        Yaml::Null => JsonValue::Null,
+        Yaml::Array(items) => JsonValue::Array(
+            items
+                .iter()
+                .map(yaml_to_json)
+                .collect::<Result<Vec<JsonValue>, String>>()?,
+        ),
+        Yaml::Hash(hash) => {
+            let mut object = serde_json::Map::new();
+            for (key, value) in hash {
+                object.insert(yaml_key(key)?, yaml_to_json(value)?);
+            }
+            JsonValue::Object(object)
+        }
+        _ => return Err("Unsupported value.".to_string()),
+    })
+}
+
+/// Merge the value into a YAML document. The document is re-emitted, so documents with
+/// comments are refused instead of silently dropping them.
+fn merge_yaml(content: &str, path: &[String], value: &Yaml) -> Result<Option<String>, String> {
+    let mut document = YamlLoader::load_from_str(content)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .next()
+        .unwrap_or(Yaml::Hash(Hash::new()));
+    let original = document.clone();
+
+    let mut node = &mut document;
+    for key in &path[..path.len() - 1] {
+        let hash = match node {
+            Yaml::Hash(hash) => hash,
+            _ => return Err(format!("'{}' is not inside a map.", key)),
+        };
+        node = hash
+            .entry(Yaml::String(key.clone()))
+            .or_insert(Yaml::Hash(Hash::new()));
+    }
+    let hash = match node {
+        Yaml::Hash(hash) => hash,
+        _ => return Err(format!("'{}' is not inside a map.", path[path.len() - 1])),
+    };
+    let key = Yaml::String(path[path.len() - 1].clone());
+    match hash.get_mut(&key) {
+        Some(existing) => merge_yaml_value(existing, value.clone()),
+        None => {
+            hash.insert(key, value.clone());
+        }
+    }
+
+    if document == original {
+        return Ok(None);
+    }
+    // the emitter drops comments, so the document is not rewritten if it contains any
+    if contains_yaml_comment(content) {
+        return Err(
+            "The YAML file contains comments that would be lost. Add the value manually."
+                .to_string(),
+        );
+    }
+
+    let mut output = String::new();
+    YamlEmitter::new(&mut output)
+        .dump(&document)
+        .map_err(|e| format!("{:?}", e))?;
+    Ok(Some(
+        output.trim_start_matches("---").trim_start().to_string(),
+    ))
+}
+
+/// Returns if the YAML content contains a comment (a `#` at the start of a line or after a
+/// whitespace that is not inside a quoted string). A `#` in a block scalar counts as well.
+fn contains_yaml_comment(content: &str) -> bool {
+    content.lines().any(|line| {
+        let mut quote = None;
+        let mut previous = ' ';
+        for c in line.chars() {
+            match (quote, c) {
+                (None, '#') if previous.is_whitespace() => return true,
+                (None, '"' | '\'') if previous.is_whitespace() || "[{,".contains(previous) => {
+                    quote = Some(c)
+                }
+                (Some(q), _) if c == q => quote = None,
+                _ => {}
+            }
+            previous = c;
+        }
+        false
+    })
+}
+
+/// Merge the new YAML value into the existing one.
+fn merge_yaml_value(existing: &mut Yaml, new_value: Yaml) {
+    match (existing, new_value) {
+        (Yaml::Hash(existing), Yaml::Hash(new_value)) => {
+            for (key, value) in new_value {
+                match existing.get_mut(&key) {
+                    Some(existing_value) => merge_yaml_value(existing_value, value),
+                    None => {
+                        existing.insert(key, value);
+                    }
+                }
+            }
+        }
+        (existing, new_value) => *existing = new_value,
+    }
+}
+
+/// Merge the value into a TOML document. The formatting and comments of the document are kept.
+fn merge_toml(content: &str, path: &[String], value: &Yaml) -> Result<Option<String>, String> {
+    let mut document = content.parse::<DocumentMut>().map_err(|e| e.to_string())?;
+
+    let mut table: &mut dyn TableLike = document.as_table_mut();
+    for key in &path[..path.len() - 1] {
+        if table.get(key).is_none() {
+            table.insert(key, toml_edit::table());
+        }
+        table = table
+            .get_mut(key)
+            .and_then(|item| item.as_table_like_mut())
+            .ok_or(format!("'{}' is not a table.", key))?;
+    }
+    merge_toml_value(table, &path[path.len() - 1], value)?;
+
+    let new_content = document.to_string();
+    if new_content.trim_end() == content.trim_end() {
+        return Ok(None);
+    }
+    Ok(Some(new_content))
+}
+
+/// Merge the value into the given key of a TOML table.
+fn merge_toml_value(table: &mut dyn TableLike, key: &str, value: &Yaml) -> Result<(), String> {
+    if let (Some(existing), Yaml::Hash(hash)) = (
+        table.get_mut(key).and_then(|item| item.as_table_like_mut()),
+        value,
+    ) {
+        for (k, v) in hash {
+            merge_toml_value(existing, &yaml_key(k)?, v)?;
+        }
+        return Ok(());
+    }
+
+    let new_value = yaml_to_toml(value)?;
+    let unchanged = table
+        .get(key)
+        .and_then(|item| item.as_value())
+        .is_some_and(|existing| existing.to_string().trim() == new_value.to_string().trim());
+    if !unchanged {
+        table.insert(key, Item::Value(new_value));
+    }
+    Ok(())
+}
+
+/// Convert a YAML value to a TOML value. Maps are converted to inline tables.
+fn yaml_to_toml(value: &Yaml) -> Result<toml_edit::Value, String> {
+    Ok(match value {
+        Yaml::String(s) => s.as_str().into(),
+        Yaml::Integer(i) => (*i).into(),
+        Yaml::Real(r) => r.parse::<f64>().map_err(|e| e.to_string())?.into(),
+        Yaml::Boolean(b) => (*b).into(),
+        Yaml::Array(items) => {
+            let mut array = toml_edit::Array::new();
+            for item in items {
+                array.push(yaml_to_toml(item)?);
+            }
+            array.into()
+        }
+        Yaml::Hash(hash) => {
+            let mut inline_table = InlineTable::new();
+            for (key, value) in hash {
+                inline_table.insert(&yaml_key(key)?, yaml_to_toml(value)?);
+            }
+            inline_table.into()
+        }
+        _ => return Err("Unsupported value.".to_string()),
+    })
+}
+
+/// Returns the given YAML map key as a string.
+fn yaml_key(key: &Yaml) -> Result<String, String> {
+    crate::types::config::yaml_to_string(key).ok_or("Unsupported map key.".to_string())
+}
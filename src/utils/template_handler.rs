@@ -1,45 +1,123 @@
 use super::rest;
 use crate::log;
-use crate::types::generate_types::FileToCreate;
+use crate::placeholder_storage::log_placeholder_errors;
+use crate::types::generate_types::{FileContext, FileToCreate, TemplateEntry};
 use crate::types::load_types::URLType;
 use crate::types::status::Status;
-use crate::types::template_meta::TemplateMeta;
+use crate::types::template_meta::{TemplateMeta, ORIGIN_FILE};
+use crate::types::template_origin::TemplateOrigin;
 use crate::utils::formater;
-use crate::utils::functions::{extract_github_tree_items, github_url_to_raw};
+use crate::utils::functions::{
+    extract_github_tree_items, get_global_templates_dir, get_templates_dir, github_url_to_raw,
+    normalize_path, run_in_parallel,
+};
 use base64::{engine::general_purpose::STANDARD, Engine as _};
 use regex::Regex;
 use reqwest::blocking::get;
 use std::io::Write;
 use std::path::Path;
 
-/// Parse the template name and check if it exists (template_name is modified in place)
-pub(crate) fn parse_template_name(name: &mut String, strict: bool) -> Status {
-    let template_name_raw = name.clone().to_string();
-    let parsed_template_name = name.clone().to_lowercase().to_string();
+/// Returns the names of all templates of the given directory (sorted by name).
+/// A directory with a `.templify.yml` is a template, a directory without one that contains
+/// templates is a namespace and its templates are named `<namespace>/<template>`.
+fn get_template_names_in(dir: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    collect_template_names(Path::new(dir), "", true, &mut names);
+    names
+}
 
-    let mut found = false;
-    let paths = std::fs::read_dir(".templates").unwrap();
+/// Collect the names of the templates in the given directory recursively. Directories on the top
+/// level without a `.templify.yml` and without nested templates are templates as well.
+fn collect_template_names(dir: &Path, prefix: &str, top_level: bool, names: &mut Vec<String>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    let mut paths = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect::<Vec<std::path::PathBuf>>();
+    paths.sort();
 
     for path in paths {
-        let path = path.unwrap().path();
+        let name = format!(
+            "{}{}",
+            prefix,
+            path.file_name().unwrap_or_default().to_string_lossy()
+        );
+        if has_meta_file(&path) {
+            names.push(name);
+            continue;
+        }
 
-        let path_name = path
-            .file_name()
-            .unwrap()
-            .to_str()
-            .unwrap()
-            .to_string()
-            .clone();
+        let count = names.len();
+        collect_template_names(&path, &format!("{}/", name), false, names);
+        if names.len() == count && top_level {
+            names.push(name);
+        }
+    }
+}
 
-        let parsed_path_name = path_name.clone().to_lowercase().to_string();
+/// Returns if the given directory contains a `.templify.yml` (or `.templify.yaml`) file.
+fn has_meta_file(dir: &Path) -> bool {
+    dir.join(".templify.yml").is_file() || dir.join(".templify.yaml").is_file()
+}
 
-        if path.is_dir() && parsed_path_name == parsed_template_name {
-            *name = path_name.clone();
+/// Returns all available templates with their origin. The templates of the project come first,
+/// global templates with the same name as a project template are shadowed and left out.
+pub(crate) fn get_templates() -> Vec<(String, TemplateOrigin)> {
+    let mut templates = get_template_names_in(&get_templates_dir())
+        .into_iter()
+        .map(|name| (name, TemplateOrigin::Project))
+        .collect::<Vec<(String, TemplateOrigin)>>();
+    for name in get_template_names_in(&get_global_templates_dir()) {
+        if !templates.iter().any(|(n, _)| *n == name) {
+            templates.push((name, TemplateOrigin::Global));
+        }
+    }
+    templates
+}
+
+/// Returns the directory of the template with the given name. A template of the project
+/// shadows a global template with the same name.
+pub(crate) fn get_template_dir(name: &str) -> String {
+    let project_dir = format!("{}/{}", get_templates_dir(), name);
+    if Path::new(&project_dir).is_dir() {
+        return project_dir;
+    }
+    let global_dir = format!("{}/{}", get_global_templates_dir(), name);
+    if Path::new(&global_dir).is_dir() {
+        return global_dir;
+    }
+    project_dir
+}
+
+/// Returns the given path without the templates directory of the project (used in messages).
+fn strip_templates_dir(path: &str) -> String {
+    path.replace(&format!("{}/", get_templates_dir()), "")
+}
+
+/// Parse the template name and check if it exists (template_name is modified in place).
+/// Templates in a namespace are addressed as `<namespace>/<template>`; the prefix matching
+/// only applies to the name of the template within its namespace.
+pub(crate) fn parse_template_name(name: &mut String, strict: bool) -> Status {
+    let template_name_raw = name.clone().to_string();
+    let parsed_template_name = name.trim_matches('/').to_lowercase();
+    let (namespace, leaf_name) = split_namespace(&parsed_template_name);
+
+    let mut found = false;
+    for (template_name, _) in get_templates() {
+        let parsed_path_name = template_name.to_lowercase();
+
+        if parsed_path_name == parsed_template_name {
+            *name = template_name.clone();
             found = true;
             break;
         }
 
-        if path.is_dir() && parsed_path_name.starts_with(parsed_template_name.as_str()) && !strict {
+        let (path_namespace, path_leaf_name) = split_namespace(&parsed_path_name);
+        if path_namespace == namespace && path_leaf_name.starts_with(leaf_name) && !strict {
             if found {
                 return Status::error(format!(
                     "Template {} is not unique. Please use a more specific name.",
@@ -47,7 +125,7 @@ pub(crate) fn parse_template_name(name: &mut String, strict: bool) -> Status {
                 ));
             }
             // assign path_name to name so that it can be used from the caller
-            *name = path_name.clone();
+            *name = template_name.clone();
             found = true;
         }
     }
@@ -58,6 +136,13 @@ pub(crate) fn parse_template_name(name: &mut String, strict: bool) -> Status {
     Status::ok()
 }
 
+/// Split a template name into its namespace (empty for top level templates) and its name.
+fn split_namespace(template_name: &str) -> (&str, &str) {
+    template_name
+        .rsplit_once('/')
+        .unwrap_or(("", template_name))
+}
+
 /// Reload a template from its source
 pub(crate) fn reload_template(name: String, strict: bool, reset: bool) -> Status {
     let mut name = name;
@@ -75,8 +160,8 @@ pub(crate) fn reload_template(name: String, strict: bool, reset: bool) -> Status
         meta.get_source()
     );
 
-    let dir = format!(".templates/{}", name);
-    let backup_dir = format!(".templates/{}---backup", name);
+    let dir = get_template_dir(&name);
+    let backup_dir = format!("{}---backup", dir);
 
     if reset && std::path::Path::new(&dir).exists() {
         if std::path::Path::new(&backup_dir).exists() {
@@ -87,12 +172,7 @@ pub(crate) fn reload_template(name: String, strict: bool, reset: bool) -> Status
 
     let url = meta.get_source();
 
-    let st = load_remote_template(
-        format!(".templates/{}", name).as_str(),
-        url.as_str(),
-        true,
-        None,
-    );
+    let st = load_remote_template(dir.as_str(), url.as_str(), true, None);
     if !st.is_ok {
         if reset {
             std::fs::rename(&backup_dir, &dir).unwrap();
@@ -108,7 +188,8 @@ pub(crate) fn reload_template(name: String, strict: bool, reset: bool) -> Status
     Status::ok()
 }
 
-/// Load a collection of templates from a remote repository
+/// Load a collection of templates from a remote repository. Namespace folders of the collection
+/// are kept and the templates in them are registered one by one.
 pub(crate) fn load_remote_template_collection(path: &str, url: &str, force: bool) -> Status {
     let url_type = match determine_url_type(url) {
         Ok(url_type) => url_type,
@@ -172,7 +253,7 @@ pub(crate) fn load_remote_template(
     if !force && Path::new(path).exists() {
         return Status::error(format!(
             "Template {} already exists...",
-            path.replace(".templates/", "")
+            strip_templates_dir(path)
         ));
     }
 
@@ -213,6 +294,29 @@ pub(crate) fn load_remote_template(
         return status;
     }
 
+    // a directory without a .templify.yml that contains templates is a namespace
+    let mut nested = Vec::new();
+    if !has_meta_file(Path::new(path)) {
+        collect_template_names(Path::new(path), "", false, &mut nested);
+    }
+    if nested.is_empty() {
+        return register_remote_template(path, url);
+    }
+    for name in nested {
+        let st = register_remote_template(
+            format!("{}/{}", path, name).as_str(),
+            format!("{}/{}", url, name).as_str(),
+        );
+        if !st.is_ok {
+            return st;
+        }
+    }
+    Status::ok()
+}
+
+/// Record the source of a loaded remote template in its `.templify.yml` (which is created if
+/// missing) and in the origin file.
+fn register_remote_template(path: &str, url: &str) -> Status {
     let mut temp_file = format!("{}/.templify.yaml", path);
 
     if !Path::new(temp_file.as_str()).exists() {
@@ -241,7 +345,16 @@ pub(crate) fn load_remote_template(
             .unwrap();
     }
 
-    log!("Loaded template: {}", path.replace(".templates/", ""));
+    // record the origin outside of the files the template controls (see ORIGIN_FILE)
+    let origin_path = format!("{}/{}", path, ORIGIN_FILE);
+    if Path::new(&origin_path).is_dir() {
+        std::fs::remove_dir_all(&origin_path).unwrap();
+    }
+    if let Err(e) = std::fs::write(&origin_path, format!("{}\n", url)) {
+        return Status::error(format!("Failed to write the file {}: {}", origin_path, e));
+    }
+
+    log!("Loaded template: {}", strip_templates_dir(path));
     Status::ok()
 }
 
@@ -308,7 +421,7 @@ fn load_remote_template_dir(path: &str, url: &str, force: bool) -> Status {
     if !force && Path::new(path).exists() {
         return Status::error(format!(
             "Directory {} already exists...",
-            path.replace(".templates/", "")
+            strip_templates_dir(path)
         ));
     }
 
@@ -368,7 +481,7 @@ fn load_remote_gitlab_template_dir(path: &str, url: &str, force: bool) -> Status
     if !force && Path::new(path).exists() {
         return Status::error(format!(
             "Directory {} already exists...",
-            path.replace(".templates/", "")
+            strip_templates_dir(path)
         ));
     }
 
@@ -422,7 +535,7 @@ fn load_remote_template_file(path: &str, url: &str, force: bool) -> Status {
     if Path::new(path).exists() && !force {
         return Status::error(format!(
             "File {} already exists...",
-            path.replace(".templates/", "")
+            strip_templates_dir(path)
         ));
     }
 
@@ -461,7 +574,7 @@ fn load_remote_gitlab_template_file(path: &str, url: &str, force: bool) -> Statu
     if Path::new(path).exists() && !force {
         return Status::error(format!(
             "File {} already exists...",
-            path.replace(".templates/", "")
+            strip_templates_dir(path)
         ));
     }
 
@@ -530,6 +643,7 @@ pub(crate) fn generate_template(
     dry_run: bool,
     meta: TemplateMeta,
     force: bool,
+    created_files: &mut Vec<String>,
 ) -> bool {
     let mut files_to_create: Vec<FileToCreate> = Vec::new();
 
@@ -545,23 +659,31 @@ pub(crate) fn generate_template(
         return false;
     }
 
-    for file in files_to_create {
-        if file.is_dir {
-            std::fs::create_dir_all(&file.path).unwrap();
-        } else {
-            let mut new_file = std::fs::File::create(&file.path).unwrap();
-            if let Some(val) = &file.file_content {
-                new_file.write_all(val.as_bytes()).unwrap();
-            }
-
-            let abs_path = std::fs::canonicalize(&file.path).unwrap();
+    // create the directories first so that the files can be written concurrently
+    for file in files_to_create.iter().filter(|file| file.is_dir) {
+        std::fs::create_dir_all(&file.path).unwrap();
+    }
 
-            log!("Created file {}", abs_path.to_str().unwrap());
+    let files = files_to_create
+        .iter()
+        .filter(|file| !file.is_dir)
+        .collect::<Vec<&FileToCreate>>();
+    let abs_paths = run_in_parallel(&files, |file| {
+        let mut new_file = std::fs::File::create(&file.path).unwrap();
+        if let Some(val) = &file.file_content {
+            new_file.write_all(val.as_bytes()).unwrap();
         }
+        std::fs::canonicalize(&file.path).unwrap()
+    });
+
+    for (file, abs_path) in files.iter().zip(abs_paths) {
+        log!("Created file {}", abs_path.to_str().unwrap());
+        created_files.push(normalize_path(&file.path));
     }
 
     true
 }
+
 /// Generate a template directory from a template
 pub(crate) fn generate_template_dir(
     path: &str,
@@ -572,74 +694,178 @@ pub(crate) fn generate_template_dir(
     force: bool,
     files_to_create: &mut Vec<FileToCreate>,
 ) -> bool {
-    let paths = std::fs::read_dir(path).unwrap();
+    for (entry, file_content) in render_template_entries(path, new_path, given_name, &meta) {
+        if entry.is_dir {
+            if !dry_run {
+                files_to_create.push(FileToCreate {
+                    file_content: None,
+                    is_dir: true,
+                    path: entry.target_path,
+                });
+            }
+            continue;
+        }
+
+        if !generate_template_file(
+            &entry.target_path,
+            file_content.unwrap_or_default(),
+            dry_run,
+            force,
+            files_to_create,
+        ) {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Returns the target paths of the template files that already exist, so that conflicts can be
+/// reported before anything (e.g. a pre-generate hook) touches the project.
+pub(crate) fn get_existing_files(
+    path: &str,
+    new_path: &str,
+    given_name: &str,
+    meta: &TemplateMeta,
+) -> Vec<String> {
+    let mut entries: Vec<TemplateEntry> = Vec::new();
+    let excluded = meta.get_hook_script_paths();
+    collect_template_entries(path, new_path, given_name, meta, &excluded, &mut entries);
+    entries
+        .into_iter()
+        .filter(|entry| !entry.is_dir && Path::new(&entry.target_path).exists())
+        .map(|entry| entry.target_path)
+        .collect()
+}
+
+/// Render all files of a template without writing them (e.g. for `-output`).
+/// Existing files are ignored because nothing is written to the target paths.
+pub(crate) fn render_template(
+    path: &str,
+    new_path: &str,
+    given_name: &str,
+    meta: &TemplateMeta,
+) -> Vec<FileToCreate> {
+    render_template_entries(path, new_path, given_name, meta)
+        .into_iter()
+        .filter(|(entry, _)| !entry.is_dir)
+        .map(|(entry, file_content)| FileToCreate {
+            path: normalize_path(&entry.target_path),
+            is_dir: false,
+            file_content,
+        })
+        .collect()
+}
+
+/// Collect the entries of a template and render the content of its files.
+/// The files are rendered concurrently, the result keeps the order of the entries.
+fn render_template_entries(
+    path: &str,
+    new_path: &str,
+    given_name: &str,
+    meta: &TemplateMeta,
+) -> Vec<(TemplateEntry, Option<String>)> {
+    let mut entries: Vec<TemplateEntry> = Vec::new();
+    let excluded = meta.get_hook_script_paths();
+    collect_template_entries(path, new_path, given_name, meta, &excluded, &mut entries);
+
+    let results = run_in_parallel(&entries, |entry| {
+        if entry.is_dir {
+            return (None, Vec::new());
+        }
+        let file_content = std::fs::read_to_string(&entry.source_path).unwrap();
+        let context = FileContext::new(&entry.target_path);
+        let (content, errors) =
+            formater::render_file_placeholders(&file_content, given_name, meta.clone(), &context);
+        (Some(content), errors)
+    });
+
+    // the workers return their errors, the logger may only be used on the main thread
+    let mut contents = Vec::new();
+    let mut errors: Vec<String> = Vec::new();
+    for (content, worker_errors) in results {
+        contents.push(content);
+        for error in worker_errors {
+            if !errors.contains(&error) {
+                errors.push(error);
+            }
+        }
+    }
+    log_placeholder_errors(errors);
+    entries.into_iter().zip(contents).collect()
+}
+
+/// Collect all files and directories of a template directory (sorted by name, directories before their content).
+/// The `excluded` files (e.g. hook scripts) and directories that only contain excluded files are skipped.
+fn collect_template_entries(
+    path: &str,
+    new_path: &str,
+    given_name: &str,
+    meta: &TemplateMeta,
+    excluded: &[String],
+    entries: &mut Vec<TemplateEntry>,
+) {
     let files_to_ignore = [
         ".templify",
         ".templify.yml",
         ".templify.yaml",
         ".tpykeep",
         ".templifykeep",
+        ORIGIN_FILE,
     ];
+
+    let mut paths = std::fs::read_dir(path)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<std::path::PathBuf>>();
+    paths.sort();
+
     for path in paths {
-        let path = path.unwrap().path();
         let file_name = path.file_name().unwrap().to_str().unwrap();
 
-        if files_to_ignore.contains(&file_name) {
+        if files_to_ignore.contains(&file_name)
+            || excluded.contains(&normalize_path(path.to_str().unwrap()))
+        {
             continue;
         }
 
-        let meta = meta.clone();
         let new_file_name = formater::handle_placeholders(file_name, given_name, meta.clone());
         let new_path = format!("{}/{}", new_path, new_file_name);
 
+        entries.push(TemplateEntry {
+            source_path: path.to_str().unwrap().to_string(),
+            target_path: new_path.clone(),
+            is_dir: path.is_dir(),
+        });
+
         if path.is_dir() {
-            if !dry_run {
-                files_to_create.push(FileToCreate {
-                    file_content: None,
-                    is_dir: true,
-                    path: new_path.clone(),
-                });
-            }
-            if !generate_template_dir(
+            let dir_index = entries.len() - 1;
+            collect_template_entries(
                 path.to_str().unwrap(),
                 &new_path,
                 given_name,
-                dry_run,
                 meta,
-                force,
-                files_to_create,
-            ) {
-                return false;
+                excluded,
+                entries,
+            );
+            let contains_excluded = excluded
+                .iter()
+                .any(|e| e.starts_with(&format!("{}/", normalize_path(path.to_str().unwrap()))));
+            if contains_excluded && entries.len() == dir_index + 1 {
+                entries.pop();
             }
-        } else if !generate_template_file(
-            path.to_str().unwrap(),
-            &new_path,
-            given_name,
-            dry_run,
-            meta,
-            force,
-            files_to_create,
-        ) {
-            return false;
         }
     }
-
-    true
 }
 
-/// Generate a file from a template
+/// Check if a rendered file can be created and add it to the files to create
 pub(crate) fn generate_template_file(
-    path: &str,
     new_path: &str,
-    given_name: &str,
+    file_content: String,
     dry_run: bool,
-    meta: TemplateMeta,
     force: bool,
     files_to_create: &mut Vec<FileToCreate>,
 ) -> bool {
-    let file_content = std::fs::read_to_string(path).unwrap();
-    let file_content = formater::handle_placeholders(&file_content, given_name, meta);
-
     if Path::new(new_path).exists() {
         if force {
             if !dry_run {
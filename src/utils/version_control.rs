@@ -1,4 +1,5 @@
 use crate::log;
+use crate::types::config::Config;
 use crate::types::version_number::VersionNumber;
 use crate::{env, utils};
 
@@ -67,6 +68,9 @@ pub fn update(v: String) -> Result<(), Box<dyn std::error::Error>> {
 
 /// This function is used to print a message if a new version of templify is available.
 pub fn print_update_message() {
+    if Config::user().get_bool("update-check") == Some(false) {
+        return;
+    }
     if !utils::functions::check_internet_connection() {
         return;
     }
@@ -0,0 +1,68 @@
+include!("../common/utils.rs");
+include!("../common/log.rs");
+include!("../common/fs.rs");
+
+pub fn test() {
+    // use a config directory inside the test directory
+    let config_home = std::env::current_dir().unwrap().join(".config");
+    let _config_home = utils::EnvVarGuard::set("XDG_CONFIG_HOME", config_home.to_str().unwrap());
+
+    utils::init_tpy();
+
+    utils::run_failure("tpy config");
+    utils::run_failure("tpy config foo");
+    utils::run_failure("tpy config get author.name");
+    utils::run_failure("tpy config set author.name");
+
+    utils::run_successfully("tpy config set author.name Jane");
+    utils::run_successfully("tpy config set vars.license MIT");
+    utils::run_successfully("tpy config set update-check false");
+    fs::dir(".config")
+        .dir("templify")
+        .file("config.yml")
+        .contains_string("name: Jane")
+        .contains_string("update-check: false");
+
+    log::clear();
+    utils::run_successfully("tpy config get author.name");
+    log::contains_line("Jane");
+
+    log::clear();
+    utils::run_successfully("tpy config list");
+    log::contains_line("author.name: Jane");
+    log::contains_line("vars.license: MIT");
+
+    // test that the config values are used when generating
+    utils::run_successfully("tpy new Config -path src");
+    fs::templates_dir()
+        .dir("Config")
+        .file(".templify.yml")
+        .append_line("vars:")
+        .append_line(" - license");
+    fs::templates_dir()
+        .dir("Config")
+        .file("$$name$$.txt")
+        .create_file("$$git-name$$ $$license$$");
+
+    utils::run_successfully("tpy generate Config test");
+    fs::dir("src").file("test.txt").contains_string("Jane MIT");
+
+    // test that acronyms are preserved
+    utils::run_successfully("tpy config set case.keep-acronyms true");
+    fs::templates_dir()
+        .dir("Config")
+        .file("$$name$$.txt")
+        .create_file("$$name.pascal$$|$$name.camel$$|$$name.kebab$$");
+    utils::run_successfully("tpy generate Config HTTPServer");
+    fs::dir("src")
+        .file("HTTPServer.txt")
+        .contains_string("HTTPServer|httpServer|http-server");
+
+    // test that a malformed config is only parsed (and reported) once per run
+    fs::dir(".config")
+        .dir("templify")
+        .file("config.yml")
+        .create_file("author: [unclosed");
+    let (_, output) = utils::run_binary("generate Config parsed", "", &[]);
+    assert_eq!(output.matches("Could not parse the config file").count(), 1);
+}
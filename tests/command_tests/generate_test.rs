@@ -141,7 +141,13 @@ pub fn test() {
         .append_line("$$name.kebab$$")
         .append_line("$$name.pascal$$")
         .append_line("$$name.macro$$")
-        .append_line("$$name.train$$");
+        .append_line("$$name.train$$")
+        .append_line("$$name.dot$$")
+        .append_line("$$name.path$$")
+        .append_line("$$name.title$$")
+        .append_line("$$name.sentence$$")
+        .append_line("$$name.flat$$")
+        .append_line("$$name.cobol$$");
     let mut file = fs::dir("src")
         .dir("commands")
         .dir("subdir")
@@ -152,6 +158,28 @@ pub fn test() {
     check_case_conversion_generated_file(&mut file);
     utils::run_successfully("tpy generate comm My-First-Component -force");
     check_case_conversion_generated_file(&mut file);
+    utils::run_successfully("tpy generate comm my.first/component -force");
+    check_case_conversion_generated_file(&mut file);
+
+    // check tokenization of acronyms, digits and unicode characters
+    fs::templates_dir()
+        .dir("Command")
+        .file("Tokens.txt")
+        .create_file("$$name.snake$$|$$name.pascal$$|$$name.camel$$");
+    let mut tokens_file = fs::dir("src")
+        .dir("commands")
+        .dir("subdir")
+        .file("Tokens.txt");
+    utils::run_successfully("tpy generate comm HTTPServer -force");
+    tokens_file.contains_string("http_server|HttpServer|httpServer");
+    utils::run_successfully("tpy generate comm v2Api -force");
+    tokens_file.contains_string("v2_api|V2Api|v2Api");
+    utils::run_successfully("tpy generate comm ärger_élan -force");
+    tokens_file.contains_string("ärger_élan|ÄrgerÉlan|ärgerÉlan");
+    fs::templates_dir()
+        .dir("Command")
+        .file("Tokens.txt")
+        .remove();
 
     // test variable placeholders
     fs::templates_dir()
@@ -211,6 +239,801 @@ pub fn test() {
         .contains_string("// ~~snippet1~~\nThis is the snippet one content for snippet_test")
         .contains_string("This is the snippet two content with default_value\n// ~~snippet2~~");
 
+    // test snippet options once, replace and sorted
+    utils::run_successfully("tpy new SnippetModes -path .");
+    fs::templates_dir()
+        .dir("SnippetModes")
+        .file(".templify.yml")
+        .append_line("snippets:")
+        .append_line("  - id: mods")
+        .append_line("    file: lib.rs")
+        .append_line("    content: mod $$name$$;")
+        .append_line("    once: true")
+        .append_line("    mode: sorted")
+        .append_line("  - id: imports")
+        .append_line("    file: lib.rs")
+        .append_line("    content: use $$name$$::run;")
+        .append_line("    before: true")
+        .append_line("    mode: sorted")
+        .append_line("  - id: main")
+        .append_line("    file: lib.rs")
+        .append_line("    content: fn main() {}")
+        .append_line("    mode: replace");
+    fs::file("lib.rs")
+        .create()
+        .append_line("use alpha::run;")
+        .append_line("use gamma::run;")
+        .append_line("// ~~imports~~")
+        .append_line("")
+        .append_line("// ~~mods~~")
+        .append_line("mod alpha;")
+        .append_line("mod gamma;")
+        .append_line("")
+        .append_line("// ~~main~~");
+    utils::run_successfully("tpy generate SnippetModes beta");
+    fs::file("lib.rs")
+        .contains_string("use alpha::run;\nuse beta::run;\nuse gamma::run;\n// ~~imports~~")
+        .contains_string("// ~~mods~~\nmod alpha;\nmod beta;\nmod gamma;\n")
+        .contains_string("fn main() {}")
+        .not_contains_string("~~main~~");
+    utils::run_successfully("tpy generate SnippetModes beta");
+    fs::file("lib.rs")
+        .contains_string("// ~~mods~~\nmod alpha;\nmod beta;\nmod gamma;\n")
+        .contains_string("use beta::run;\nuse beta::run;");
+    log::contains_line("Snippet 'mods' is already present in file 'lib.rs'.");
+
+    // test snippet anchors
+    utils::run_successfully("tpy new SnippetAnchors -path .");
+    fs::templates_dir()
+        .dir("SnippetAnchors")
+        .file(".templify.yml")
+        .append_line("snippets:")
+        .append_line("  - id: route")
+        .append_line("    file: router.ts")
+        .append_line("    content: '  \"$$name$$\",'")
+        .append_line("    within: ^const routes...^\\]")
+        .append_line("    match: last")
+        .append_line("  - id: import")
+        .append_line("    file: router.ts")
+        .append_line("    content: import $$name$$;")
+        .append_line("    insert-after: ^import")
+        .append_line("    match: last")
+        .append_line("  - id: type")
+        .append_line("    file: router.ts")
+        .append_line("    content: type Route = string;")
+        .append_line("    insert-before: ^const routes")
+        .append_line("  - id: section")
+        .append_line("    file: router.ts")
+        .append_line("    content: // section")
+        .append_line("    before: ^const routes")
+        .append_line("    match: last")
+        .append_line("  - id: header")
+        .append_line("    file: router.ts")
+        .append_line("    content: // generated")
+        .append_line("    at: start")
+        .append_line("  - id: footer")
+        .append_line("    file: router.ts")
+        .append_line("    content: export default routes;")
+        .append_line("    at: end");
+    fs::file("router.ts")
+        .create()
+        .append_line("import a;")
+        .append_line("import b;")
+        .append_line("const routes = [")
+        .append_line("  \"a\",")
+        .append_line("];")
+        .append_line("const routes = [")
+        .append_line("];");
+    utils::run_successfully("tpy generate SnippetAnchors c");
+    fs::file("router.ts")
+        .contains_string("// generated\n\nimport a;")
+        .contains_string("import b;\nimport c;\ntype Route = string;\nconst routes")
+        .contains_string(
+            "const routes = [\n  \"a\",\n];\n// section\nconst routes = [\n  \"c\",\n];",
+        )
+        .contains_string("];\nexport default routes;");
+
+    fs::templates_dir()
+        .dir("SnippetAnchors")
+        .file(".templify.yml")
+        .append_line("  - id: missing")
+        .append_line("    file: router.ts")
+        .append_line("    content: missing")
+        .append_line("    after: ^does not exist")
+        .append_line("    required: true");
+    utils::run_failure("tpy generate SnippetAnchors d");
+    log::contains_string(
+        "Could not find the anchor '^does not exist' of snippet 'missing' in file 'router.ts'.",
+    );
+
+    // test the snippet summary and strict snippets
+    utils::run_successfully("tpy new SnippetStrict -path strict");
+    fs::templates_dir()
+        .dir("SnippetStrict")
+        .file("$$name$$.txt")
+        .create();
+    fs::templates_dir()
+        .dir("SnippetStrict")
+        .file(".templify.yml")
+        .append_line("snippets:")
+        .append_line("  - id: present")
+        .append_line("    file: strict.txt")
+        .append_line("    content: $$name$$")
+        .append_line("  - id: absent")
+        .append_line("    file: strict.txt")
+        .append_line("    content: $$name$$")
+        .append_line("  - id: nofile")
+        .append_line("    file: missing.txt")
+        .append_line("    content: $$name$$");
+    fs::file("strict.txt").create_file("~~present~~");
+    log::clear();
+    utils::run_failure("tpy generate SnippetStrict first -strict-snippets");
+    log::contains_string("Could not find the marker '~~absent~~' of snippet 'absent'");
+    fs::dir("strict").file("first.txt").check_not_exists();
+    fs::file("strict.txt").has_content("~~present~~");
+    log::clear();
+    utils::run_successfully("tpy generate SnippetStrict first");
+    log::contains_line("Snippets: 1 inserted, 0 already present, 1 anchor missing, 1 file missing");
+    log::contains_line("Snippet 'present' inserted into file 'strict.txt'.");
+    log::contains_line(
+        "Could not find the marker '~~absent~~' of snippet 'absent' in file 'strict.txt'.",
+    );
+    log::contains_line(
+        "Cannot create snippet 'nofile' because the file 'missing.txt' does not exist.",
+    );
+    fs::dir("strict").file("first.txt").check_all_exists();
+    fs::templates_dir()
+        .dir("SnippetStrict")
+        .file(".templify.yml")
+        .append_line("    required: true");
+    utils::run_failure("tpy generate SnippetStrict second");
+    log::contains_string(
+        "Cannot create snippet 'nofile' because the file 'missing.txt' does not exist.",
+    );
+
+    // test snippets that target multiple files
+    utils::run_successfully("tpy new SnippetGlob -path .");
+    fs::templates_dir()
+        .dir("SnippetGlob")
+        .file(".templify.yml")
+        .append_line("snippets:")
+        .append_line("  - id: barrel")
+        .append_line("    file: barrels/**/index.ts")
+        .append_line("    content: export * from './$$name$$';")
+        .append_line("    at: end")
+        .append_line("  - id: locale")
+        .append_line("    file:")
+        .append_line("      - locales/en.txt")
+        .append_line("      - locales/de.txt")
+        .append_line("    content: $$name$$")
+        .append_line("    at: end")
+        .append_line("  - id: nomatch")
+        .append_line("    file: nothing/**/*.ts")
+        .append_line("    content: $$name$$");
+    fs::dir("barrels").create().file("index.ts").create();
+    fs::dir("barrels")
+        .dir("ui")
+        .create()
+        .file("index.ts")
+        .create();
+    fs::dir("barrels").dir("ui").file("button.ts").create();
+    fs::dir("locales").create().file("en.txt").create();
+    fs::dir("locales").file("de.txt").create();
+    log::clear();
+    utils::run_successfully("tpy generate SnippetGlob widget");
+    fs::dir("barrels")
+        .file("index.ts")
+        .contains_string("export * from './widget';");
+    fs::dir("barrels")
+        .dir("ui")
+        .file("index.ts")
+        .contains_string("export * from './widget';");
+    fs::dir("barrels")
+        .dir("ui")
+        .file("button.ts")
+        .not_contains_string("widget");
+    fs::dir("locales").file("en.txt").contains_string("widget");
+    fs::dir("locales").file("de.txt").contains_string("widget");
+    log::contains_line("Snippets: 4 inserted, 0 already present, 0 anchor missing, 1 file missing");
+    log::contains_line("Snippet 'barrel' inserted into file 'barrels/ui/index.ts'.");
+    log::contains_line(
+        "Cannot create snippet 'nomatch' because no file matches 'nothing/**/*.ts'.",
+    );
+
+    // test structured snippets
+    utils::run_successfully("tpy new SnippetStructured -path .");
+    fs::templates_dir()
+        .dir("SnippetStructured")
+        .file(".templify.yml")
+        .append_line("snippets:")
+        .append_line("  - id: npm")
+        .append_line("    file: package.json")
+        .append_line("    path: dependencies.$$name.kebab$$")
+        .append_line("    value: ^1.0.0")
+        .append_line("  - id: cargo")
+        .append_line("    file: Cargo.toml")
+        .append_line("    path: dependencies.$$name.snake$$")
+        .append_line("    value:")
+        .append_line("      version: '1.0'")
+        .append_line("      features: [full]")
+        .append_line("  - id: routes")
+        .append_line("    file: routes.yml")
+        .append_line("    path: routes.$$name.kebab$$")
+        .append_line("    value: /$$name.kebab$$");
+    fs::file("package.json").create_file(
+        "{\n    \"name\": \"app\",\n    \"dependencies\": {\n        \"react\": \"^18.0.0\"\n    }\n}\n",
+    );
+    fs::file("Cargo.toml")
+        .create_file("[package]\nname = \"app\" # the name\n\n[dependencies]\nregex = \"1\"\n");
+    fs::file("routes.yml").create_file("routes:\n  home: /\n");
+    utils::run_successfully("tpy generate SnippetStructured myLib");
+    fs::file("package.json").has_content(
+        "{\n    \"name\": \"app\",\n    \"dependencies\": {\n        \"react\": \"^18.0.0\",\n        \"my-lib\": \"^1.0.0\"\n    }\n}\n",
+    );
+    fs::file("Cargo.toml")
+        .contains_string("name = \"app\" # the name")
+        .contains_string("regex = \"1\"\nmy_lib = { version = \"1.0\", features = [\"full\"] }\n");
+    fs::file("routes.yml").has_content("routes:\n  home: /\n  my-lib: /my-lib\n");
+    log::clear();
+    utils::run_successfully("tpy generate SnippetStructured myLib");
+    log::contains_line("Snippets: 0 inserted, 3 already present, 0 anchor missing, 0 file missing");
+
+    // test quoted and escaped keys in the path and YAML files with comments
+    utils::run_successfully("tpy new SnippetKeys -path keys");
+    fs::templates_dir()
+        .dir("SnippetKeys")
+        .file(".templify.yml")
+        .append_line("snippets:")
+        .append_line("  - id: scoped")
+        .append_line("    file: keys.json")
+        .append_line("    path: dependencies.\"@scope/$$name$$.js\"")
+        .append_line("    value: ^2.0.0")
+        .append_line("  - id: escaped")
+        .append_line("    file: keys.json")
+        .append_line("    path: settings.editor\\.tabSize")
+        .append_line("    value: 4");
+    fs::file("keys.json").create_file("{}");
+    utils::run_successfully("tpy generate SnippetKeys pkg");
+    fs::file("keys.json")
+        .contains_string("\"@scope/pkg.js\": \"^2.0.0\"")
+        .contains_string("\"editor.tabSize\": 4");
+    fs::file("routes.yml").create_file("# the routes\nroutes:\n  home: / # start\n");
+    utils::run_failure("tpy generate SnippetStructured otherLib");
+    log::contains_string("The YAML file contains comments that would be lost.");
+    fs::file("routes.yml").has_content("# the routes\nroutes:\n  home: / # start\n");
+
+    // test the snippet preview in dry-run mode
+    utils::run_successfully("tpy new SnippetPreview -path .");
+    fs::templates_dir()
+        .dir("SnippetPreview")
+        .file(".templify.yml")
+        .append_line("snippets:")
+        .append_line("  - id: item")
+        .append_line("    file: preview.txt")
+        .append_line("    content: $$name$$")
+        .append_line("  - id: missing")
+        .append_line("    file: preview.txt")
+        .append_line("    content: $$name$$");
+    fs::file("preview.txt").create_file("one\ntwo\nthree\n~~item~~\nfour\nfive\nsix\n");
+    log::clear();
+    utils::run_successfully("tpy generate SnippetPreview inserted -dry-run");
+    log::contains_lines_in_order(&[
+        "Would insert snippet 'item' into file 'preview.txt' at the marker '~~item~~':",
+        "    three",
+        "    ~~item~~",
+        "  + inserted",
+        "    four",
+        "    five",
+    ]);
+    log::contains_line("Would skip snippet: Could not find the marker '~~missing~~' of snippet 'missing' in file 'preview.txt'.");
+    log::not_contains_line("two");
+    log::not_contains_line("six");
+    fs::file("preview.txt").has_content("one\ntwo\nthree\n~~item~~\nfour\nfive\nsix\n");
+
+    // test multi-line snippet content with auto-indent and dedent
+    utils::run_successfully("tpy new SnippetBlock -path .");
+    fs::templates_dir()
+        .dir("SnippetBlock")
+        .file(".templify.yml")
+        .append_line("snippets:")
+        .append_line("  - id: handler")
+        .append_line("    file: main.c")
+        .append_line("    auto-indent: true")
+        .append_line("    dedent: true")
+        .append_line("    content: |")
+        .append_line("          if (x == $$name$$) {")
+        .append_line("              printf(\"$$name$$\\n\");")
+        .append_line("          }")
+        .append_line("  - id: escaped")
+        .append_line("    file: main.c")
+        .append_line("    content: 'puts(\"a\\\\n\");\\nputs(\"b\");'");
+    fs::file("main.c")
+        .create()
+        .append_line("int main() {")
+        .append_line("    // ~~handler~~")
+        .append_line("}")
+        .append_line("// ~~escaped~~");
+    utils::run_successfully("tpy generate SnippetBlock 42");
+    fs::file("main.c")
+        .contains_string(
+            "    // ~~handler~~\n    if (x == 42) {\n        printf(\"42\\n\");\n    }\n}",
+        )
+        .contains_string("// ~~escaped~~\nputs(\"a\\n\");\nputs(\"b\");");
+
+    // test that dedent only strips the identical whitespace prefix (tabs, spaces and wide spaces)
+    utils::run_successfully("tpy new SnippetDedent -path .");
+    fs::templates_dir()
+        .dir("SnippetDedent")
+        .file(".templify.yml")
+        .append_line("snippets:")
+        .append_line("  - id: tabs")
+        .append_line("    file: dedent.txt")
+        .append_line("    dedent: true")
+        .append_line("    content: \"\\t  if (a) {\\n\\t    b();\\n\\t  }\"")
+        .append_line("  - id: mixed")
+        .append_line("    file: dedent.txt")
+        .append_line("    dedent: true")
+        .append_line("    content: \"\\tone\\n    two\"")
+        .append_line("  - id: wide")
+        .append_line("    file: dedent.txt")
+        .append_line("    dedent: true")
+        .append_line("    content: \"\\u3000wide\\n  narrow\"");
+    fs::file("dedent.txt")
+        .create()
+        .append_line("~~tabs~~")
+        .append_line("~~mixed~~")
+        .append_line("~~wide~~");
+    utils::run_successfully("tpy generate SnippetDedent x");
+    fs::file("dedent.txt")
+        .contains_string("~~tabs~~\nif (a) {\n  b();\n}\n")
+        .contains_string("~~mixed~~\n\tone\n    two\n")
+        .contains_string("~~wide~~\n\u{3000}wide\n  narrow");
+
+    // test that line endings, the final newline and the BOM are preserved by snippets
+    utils::run_successfully("tpy new SnippetFormat -path .");
+    fs::templates_dir()
+        .dir("SnippetFormat")
+        .file(".templify.yml")
+        .append_line("snippets:")
+        .append_line("  - id: crlf")
+        .append_line("    file: crlf.txt")
+        .append_line("    content: $$name$$")
+        .append_line("  - id: lf")
+        .append_line("    file: lf.txt")
+        .append_line("    content: $$name$$");
+    fs::file("crlf.txt").create_file("\u{feff}first\r\n~~crlf~~\r\nlast\r\n");
+    fs::file("lf.txt").create_file("first\n~~lf~~\nlast");
+    utils::run_successfully("tpy generate SnippetFormat inserted");
+    fs::file("crlf.txt").has_content("\u{feff}first\r\n~~crlf~~\r\ninserted\r\nlast\r\n");
+    fs::file("lf.txt").has_content("first\n~~lf~~\ninserted\nlast");
+
+    // test pre and post generate hooks
+    utils::run_successfully("tpy new Hooked -path hooked/$$name$$");
+    fs::templates_dir()
+        .dir("Hooked")
+        .file("$$name$$.txt")
+        .create_file("$$name$$");
+    fs::templates_dir()
+        .dir("Hooked")
+        .dir("hooks")
+        .create()
+        .file("post.sh")
+        .create_file("echo \"$TPY_NAME $TPY_VAR_LANG_NAME $TPY_FILES\" > post.log\n")
+        .append_line("cat \"$TPY_FILES\" > copy.log");
+    fs::templates_dir()
+        .dir("Hooked")
+        .dir("hooks")
+        .file("it's $HOME.sh")
+        .create_file("#!/usr/bin/env bash")
+        .append_line("[[ -d \"$TPY_TEMPLATE_DIR\" ]] && echo bash > \"$TPY_OUTPUT_DIR/bash.log\"");
+    fs::templates_dir()
+        .dir("Hooked")
+        .file(".templify.yml")
+        .append_line("vars:")
+        .append_line("  - lang-name(rust)")
+        .append_line("hooks:")
+        .append_line("  pre_generate:")
+        .append_line("    - echo \"pre $$name$$\" > pre.log")
+        .append_line("  post_generate:")
+        .append_line("    - script: hooks/post.sh")
+        .append_line("      cwd: output")
+        .append_line("    - script: hooks/it's $HOME.sh")
+        .append_line("      cwd: output")
+        .append_line("    - command: exit 3")
+        .append_line("      continue_on_error: true")
+        .append_line("    - echo after > after.log");
+    utils::run_successfully("tpy generate Hooked hookTest -default-var");
+    fs::file("pre.log").contains_string("pre hookTest");
+    fs::dir("hooked")
+        .dir("hookTest")
+        .file("post.log")
+        .contains_string(&format!(
+            "hookTest rust {}/hooked/hookTest/hookTest.txt",
+            std::env::current_dir().unwrap().to_string_lossy()
+        ));
+    // the paths of the environment are absolute and the shebang of a script is used
+    fs::dir("hooked")
+        .dir("hookTest")
+        .file("copy.log")
+        .has_content("hookTest");
+    fs::dir("hooked")
+        .dir("hookTest")
+        .file("bash.log")
+        .has_content("bash\n");
+    fs::dir("hooked")
+        .dir("hookTest")
+        .dir("hooks")
+        .check_not_exists();
+    fs::file("after.log").contains_string("after");
+
+    // test that existing files are reported before the pre-generate hooks run
+    fs::file("pre.log").remove();
+    utils::run_failure("tpy generate Hooked hookTest -default-var");
+    log::contains_line("File hooked/hookTest/hookTest.txt already exists.");
+    fs::file("pre.log").check_not_exists();
+
+    fs::templates_dir()
+        .dir("Hooked")
+        .file(".templify.yml")
+        .append_line("  pre_generate:")
+        .append_line("    - exit 1");
+    log::clear();
+    utils::run_failure("tpy generate Hooked failing -default-var");
+    fs::dir("hooked").dir("failing").check_not_exists();
+    assert_eq!(templify::env::get_exit_code(), 1);
+
+    // test streamed hook output, exit codes and timeouts
+    utils::run_successfully("tpy new Streamed -path .");
+    fs::templates_dir()
+        .dir("Streamed")
+        .file(".templify.yml")
+        .append_line("hooks:")
+        .append_line("  pre_generate:")
+        .append_line("    - echo streamed-out && echo streamed-err >&2")
+        .append_line("    - command: exit $TPY_VAR_CODE")
+        .append_line("    - command: (sleep 1; echo leaked > leaked.log); echo done")
+        .append_line("      timeout: 0.5")
+        .append_line("vars:")
+        .append_line("  - code(0)");
+    log::clear();
+    utils::run_failure("tpy generate Streamed streamed -default-var");
+    log::contains_line("streamed-out");
+    log::contains_line("ERROR: streamed-err");
+    log::contains_string("Command timed out after 0.5 seconds");
+    assert_eq!(templify::env::get_exit_code(), 124);
+    // the whole process group is killed, so the subshell of the command does not survive
+    std::thread::sleep(std::time::Duration::from_millis(1500));
+    fs::file("leaked.log").check_not_exists();
+    utils::run_failure("tpy generate Streamed streamed -var code=3");
+    log::contains_string("exited with code 3");
+    assert_eq!(templify::env::get_exit_code(), 3);
+
+    // test that the exit code of the legacy command fails the run
+    utils::run_successfully("tpy new Legacy -path legacy");
+    fs::templates_dir()
+        .dir("Legacy")
+        .file(".templify.yml")
+        .append_line("command: echo legacy && exit 5");
+    utils::run_failure("tpy generate Legacy legacy");
+    log::contains_string("exited with code 5");
+    assert_eq!(templify::env::get_exit_code(), 5);
+
+    // test that hooks of remote templates can be disabled
+    let config_home = std::env::current_dir().unwrap().join(".config");
+    let _config_home = utils::EnvVarGuard::set("XDG_CONFIG_HOME", config_home.to_str().unwrap());
+    utils::run_successfully("tpy new RemoteHooked -path .");
+    fs::templates_dir()
+        .dir("RemoteHooked")
+        .file(".templify.yml")
+        .append_line("hooks:")
+        .append_line("  post_generate:")
+        .append_line("    - echo ran > remote.log")
+        .append_line(".source: https://github.com/example/templates/tree/main/RemoteHooked");
+    log::clear();
+    utils::run_successfully("tpy generate RemoteHooked first -no-hooks");
+    log::contains_line("Skipping 1 hook(s) because of -no-hooks.");
+    fs::file("remote.log").check_not_exists();
+    utils::run_successfully("tpy config set hooks.allow-remote false");
+    utils::run_successfully("tpy generate RemoteHooked second");
+    log::contains_line(
+        "Skipping 1 hook(s) because hooks of remote templates are disabled (hooks.allow-remote).",
+    );
+    fs::file("remote.log").check_not_exists();
+    utils::run_successfully("tpy config set hooks.allow-remote true");
+    utils::run_successfully("tpy generate RemoteHooked third -dry-run");
+    log::contains_line("Would execute post-generate hook: Command: echo ran > remote.log");
+    fs::file("remote.log").check_not_exists();
+
+    // test the confirmation of hooks of remote templates (the origin file wins over .source)
+    utils::run_successfully("tpy config set update-check false");
+    utils::run_successfully("tpy new Confirmed -path confirmed");
+    let mut confirmed = fs::templates_dir().dir("Confirmed");
+    confirmed
+        .file(".templify.yml")
+        .append_line("hooks:")
+        .append_line("  post_generate:")
+        .append_line("    - echo ran >> confirmed.log")
+        .append_line(".source: \"\"");
+    confirmed
+        .file(".templify-origin")
+        .create_file("https://github.com/example/templates/tree/main/Confirmed");
+    let (success, output) = utils::run_binary("generate Confirmed refused", "", &[]);
+    assert!(!success);
+    assert!(output.contains("The hooks of the template Confirmed were not confirmed."));
+    fs::file("confirmed.log").check_not_exists();
+
+    let interactive = [("TPY_INTERACTIVE", "1")];
+    let (success, output) = utils::run_binary("generate Confirmed once", "y\n", &interactive);
+    assert!(success);
+    assert!(output.contains("Do you want to execute them?"));
+    fs::file("confirmed.log").has_content("ran\n");
+    let (success, _) = utils::run_binary("generate Confirmed declined", "n\n", &interactive);
+    assert!(!success);
+    let (success, _) = utils::run_binary("generate Confirmed still", "", &[]);
+    assert!(!success);
+
+    let (success, _) = utils::run_binary("generate Confirmed trusted", "a\n", &interactive);
+    assert!(success);
+    let (success, output) = utils::run_binary("generate Confirmed again", "", &[]);
+    assert!(success);
+    assert!(!output.contains("Do you want to execute them?"));
+    fs::file("confirmed.log").has_content("ran\nran\nran\n");
+    confirmed.file("changed.txt").create_file("changed");
+    let (success, _) = utils::run_binary("generate Confirmed changed", "", &[]);
+    assert!(!success);
+    fs::file("confirmed.log").has_content("ran\nran\nran\n");
+
+    // test formatters from the project config
+    utils::run_successfully("tpy new Formatted -path fmt");
+    let mut formatted = fs::templates_dir().dir("Formatted");
+    formatted
+        .file("$$name$$.rs")
+        .create_file("fn $$name$$() {}");
+    formatted.file("other.rs").create_file("// other");
+    formatted.file("notes.txt").create_file("notes");
+    formatted
+        .file(".templify.yml")
+        .append_line("snippets:")
+        .append_line("  - id: mods")
+        .append_line("    file: fmt-lib.rs")
+        .append_line("    content: mod $$name$$;");
+    fs::file("fmt-lib.rs").create_file("// ~~mods~~");
+    fs::file(".templifyrc.yml")
+        .create_file("formatters:")
+        .append_line("  \"*.rs\": \"echo rs {files} >> fmt.log\"")
+        .append_line("  \"*.txt\": exit 2");
+    // the formatter commands of the project config have to be confirmed
+    utils::run_failure("tpy generate Formatted fmtTest");
+    log::contains_string("The commands of the project config were not confirmed.");
+    fs::file("fmt.log").check_not_exists();
+    let (success, output) = utils::run_binary("placeholder", "a\n", &[("TPY_INTERACTIVE", "1")]);
+    assert!(success);
+    assert!(output.contains("  formatter *.rs: echo rs {files} >> fmt.log"));
+    log::clear();
+    utils::run_successfully("tpy generate Formatted fmtTest");
+    fs::file("fmt.log").has_content("rs fmt/fmtTest.rs fmt/other.rs fmt-lib.rs\n");
+    log::contains_line("ERROR: Formatter for *.txt failed with exit code 2.");
+    log::contains_line("Files generated successfully.");
+    // the file paths are passed to the shell without being interpreted
+    fs::file("fmt.log").remove();
+    utils::run_successfully("tpy generate Formatted it's$HOME -force");
+    fs::file("fmt.log").has_content("rs fmt/it's$HOME.rs fmt/other.rs fmt-lib.rs\n");
+    fs::file(".templifyrc.yml").remove();
+
+    // test rendering the output to stdout or an archive
+    utils::run_successfully("tpy new Packaged -path out/$$name$$");
+    let mut packaged = fs::templates_dir().dir("Packaged");
+    packaged.file("$$name$$.txt").create_file("name $$name$$");
+    packaged.file("readme.md").create_file("# $$name.pascal$$");
+    packaged
+        .file(".templify.yml")
+        .append_line("hooks:")
+        .append_line("  pre_generate:")
+        .append_line("    - echo ran > packaged.log");
+    log::clear();
+    utils::run_successfully("tpy generate Packaged pkg -output tar:dist/pkg.tar");
+    log::contains_line("Skipping 1 hook(s) because of -output.");
+    log::contains_line("Packaged 2 file(s) into dist/pkg.tar.");
+    fs::dir("dist")
+        .file("pkg.tar")
+        .contains_string("out/pkg/pkg.txt")
+        .contains_string("name pkg")
+        .contains_string("out/pkg/readme.md")
+        .contains_string("# Pkg");
+    utils::run_successfully("tpy generate Packaged zipped -output zip:dist/zipped.zip");
+    let archive = std::fs::File::open("dist/zipped.zip").unwrap();
+    let mut archive = zip::ZipArchive::new(archive).unwrap();
+    let mut content = String::new();
+    std::io::Read::read_to_string(
+        &mut archive.by_name("out/zipped/zipped.txt").unwrap(),
+        &mut content,
+    )
+    .unwrap();
+    assert_eq!(content, "name zipped");
+    assert_eq!(archive.len(), 2);
+    utils::run_successfully("tpy generate Packaged printed -output stdout");
+    log::contains_line("Rendered 2 file(s) to stdout.");
+    packaged
+        .file("$$name$$.txt")
+        .create_file("$$lang$$ $$kind$$ $$name$$");
+    packaged.file("readme.md").remove();
+    packaged
+        .file(".templify.yml")
+        .append_line("vars:")
+        .append_line(" - lang")
+        .append_line(" - kind[lib,bin]");
+    let binary_env = [("XDG_CONFIG_HOME", config_home.to_str().unwrap())];
+    let (success, stdout, stderr) = utils::run_binary_split(
+        "generate Packaged piped -output stdout",
+        "rust\n2\n",
+        &binary_env,
+    );
+    assert!(success);
+    assert_eq!(stdout, "rust bin piped");
+    assert!(stderr.contains("lang: "));
+    assert!(stderr.contains("[2] bin"));
+    utils::run_failure("tpy generate Packaged invalid -output rar:pkg.rar");
+    log::contains_string("Invalid output 'rar:pkg.rar'. Use stdout, tar:<file> or zip:<file>.");
+    fs::dir("out").check_not_exists();
+    fs::file("packaged.log").check_not_exists();
+
+    // test that the project root is found from a subdirectory
+    utils::run_successfully("tpy new Rooted -path rooted");
+    let mut rooted = fs::templates_dir().dir("Rooted");
+    rooted.file("$$name$$.txt").create_file("$$output-dir$$");
+    rooted
+        .file(".templify.yml")
+        .append_line("snippets:")
+        .append_line("  - id: rooted")
+        .append_line("    file: rooted-index.txt")
+        .append_line("    content: $$name$$")
+        .append_line("hooks:")
+        .append_line("  post_generate:")
+        .append_line("    - pwd > rooted-hook.log");
+    fs::file("rooted-index.txt").create_file("// ~~rooted~~");
+    fs::dir("src").dir("nested").create();
+    let project_root = std::env::current_dir().unwrap();
+    std::env::set_current_dir(project_root.join("src/nested")).unwrap();
+    utils::run_successfully("tpy generate Rooted fromRoot");
+    utils::run_successfully("tpy generate Rooted fromHere -here -output tar:here.tar");
+    utils::run_successfully("tpy generate Rooted fromHere -here");
+    assert_eq!(
+        std::env::current_dir().unwrap(),
+        project_root.join("src/nested")
+    );
+    std::env::set_current_dir(&project_root).unwrap();
+    fs::dir("rooted").file("fromRoot.txt").has_content("rooted");
+    fs::file("rooted-index.txt")
+        .contains_string("fromRoot")
+        .contains_string("fromHere");
+    fs::file("rooted-hook.log").contains_string(project_root.to_str().unwrap());
+    fs::dir("src")
+        .dir("nested")
+        .dir("rooted")
+        .file("fromHere.txt")
+        .has_content("src/nested/rooted");
+    fs::dir("src")
+        .dir("nested")
+        .file("here.tar")
+        .contains_string("src/nested/rooted/fromHere.txt");
+
+    // test templates of the global template library
+    let data_home = std::env::current_dir().unwrap().join(".data");
+    let xdg_data_home = utils::EnvVarGuard::set("XDG_DATA_HOME", data_home.to_str().unwrap());
+    let global_dir = data_home.join("templify/templates");
+    std::fs::create_dir_all(global_dir.join("Personal")).unwrap();
+    std::fs::write(global_dir.join("Personal/.templify.yml"), "path: personal").unwrap();
+    std::fs::write(global_dir.join("Personal/$$name$$.txt"), "global $$name$$").unwrap();
+    std::fs::create_dir_all(global_dir.join("Rooted")).unwrap();
+    std::fs::write(global_dir.join("Rooted/global.txt"), "global").unwrap();
+    utils::run_successfully("tpy generate pers mine");
+    fs::dir("personal")
+        .file("mine.txt")
+        .has_content("global mine");
+    utils::run_successfully("tpy generate Rooted shadowTest");
+    fs::dir("rooted").file("shadowTest.txt").check_all_exists();
+    fs::dir("rooted").file("global.txt").check_not_exists();
+    drop(xdg_data_home);
+
+    // test a configured templates directory
+    fs::file(".templifyrc.yml").create_file("templates-dir: tools/templates/");
+    utils::run_successfully("tpy init -blank");
+    fs::dir("tools").dir("templates").check_all_exists();
+    utils::run_successfully("tpy new Tooled -path tooled");
+    fs::dir("tools")
+        .dir("templates")
+        .dir("Tooled")
+        .file("$$name$$.txt")
+        .create_file("tooled $$name$$");
+    fs::dir("src").dir("tooled").create();
+    std::env::set_current_dir(project_root.join("src/tooled")).unwrap();
+    utils::run_successfully("tpy generate Tooled toolTest");
+    std::env::set_current_dir(&project_root).unwrap();
+    fs::dir("tooled")
+        .file("toolTest.txt")
+        .has_content("tooled toolTest");
+    utils::run_failure("tpy generate Rooted notFound");
+    log::contains_string("Template Rooted not found.");
+    let templates_dir = utils::EnvVarGuard::set("TPY_TEMPLATES_DIR", "missing/templates");
+    utils::run_failure("tpy list");
+    log::contains_string("templify is not initialized in this project.");
+    drop(templates_dir);
+    let templates_dir = utils::EnvVarGuard::set("TPY_TEMPLATES_DIR", ".templates");
+    utils::run_successfully("tpy generate Rooted envTest");
+    drop(templates_dir);
+    fs::file(".templifyrc.yml").remove();
+    fs::dir("rooted").file("envTest.txt").check_all_exists();
+
+    // test templates in namespaces
+    utils::run_successfully("tpy new react/component -path src/react");
+    utils::run_successfully("tpy new react/container -path src/react");
+    utils::run_successfully("tpy new rust/module -path src/rust");
+    fs::templates_dir()
+        .dir("react")
+        .dir("component")
+        .file("$$name$$.tsx")
+        .create_file("$$template-name.pascal$$ $$name$$");
+    log::clear();
+    utils::run_successfully("tpy list");
+    log::contains_line("react/component");
+    log::contains_line("react/container");
+    log::contains_line("rust/module");
+    log::not_contains_line("react");
+    utils::run_successfully("tpy generate react/comp Button");
+    fs::dir("src")
+        .dir("react")
+        .file("Button.tsx")
+        .has_content("Component Button");
+    utils::run_failure("tpy generate react/co Button");
+    log::contains_string("Template react/co is not unique.");
+    utils::run_failure("tpy generate module Button");
+    log::contains_string("Template module not found.");
+    utils::run_failure("tpy generate react/comp Input -strict");
+    utils::run_successfully("tpy generate React/Component Input -strict");
+    fs::dir("src")
+        .dir("react")
+        .file("Input.tsx")
+        .check_all_exists();
+
+    // test that large templates are generated in a deterministic order
+    utils::run_successfully("tpy new Many -path many");
+    let mut many = fs::templates_dir().dir("Many");
+    many.file("c.txt").create_file("c $$name$$");
+    many.file("a.txt").create_file("a $$name$$");
+    many.dir("b")
+        .create()
+        .file("d.txt")
+        .create_file("d $$name$$");
+    for i in 0..50 {
+        many.dir("e")
+            .create()
+            .file(&format!("file{:02}.txt", i))
+            .create_file(&format!("{} $$name.kebab$$", i));
+    }
+    log::clear();
+    utils::run_successfully("tpy generate Many parallelTest -dry-run");
+    log::contains_lines_in_order(&[
+        "Would create file many/a.txt",
+        "Would create file many/b/d.txt",
+        "Would create file many/c.txt",
+        "Would create file many/e/file00.txt",
+        "Would create file many/e/file49.txt",
+    ]);
+    utils::run_successfully("tpy generate Many parallelTest");
+    fs::dir("many")
+        .file("a.txt")
+        .contains_string("a parallelTest");
+    fs::dir("many")
+        .dir("b")
+        .file("d.txt")
+        .contains_string("d parallelTest");
+    for i in 0..50 {
+        fs::dir("many")
+            .dir("e")
+            .file(&format!("file{:02}.txt", i))
+            .contains_string(&format!("{} parallel-test", i));
+    }
+
     // test -reload flag
     utils::run_successfully(
         "tpy load https://github.com/cophilot/templify-vault/tree/main/Test/MyTest -t",
@@ -249,5 +1072,10 @@ fn check_case_conversion_generated_file(file: &mut FSItem) {
         .contains_string("my-first-component")
         .contains_string("MyFirstComponent")
         .contains_string("MY_FIRST_COMPONENT")
-        .contains_string("My-First-Component");
+        .contains_string("My-First-Component")
+        .contains_string("my.first.component")
+        .contains_string("my/first/component")
+        .contains_string("My First Component")
+        .contains_string("My first component")
+        .contains_string("MY-FIRST-COMPONENT");
 }
@@ -66,4 +66,31 @@ pub fn test() {
     log::clear();
     utils::run_successfully("tpy list -path");
     log::contains_line("Test4 - test4_description [test4/path]");
+
+    // test the templates of the global template library
+    let data_home = std::env::current_dir().unwrap().join(".data");
+    let _data_home = utils::EnvVarGuard::set("XDG_DATA_HOME", data_home.to_str().unwrap());
+    let global_dir = data_home.join("templify/templates");
+    std::fs::create_dir_all(global_dir.join("Global1")).unwrap();
+    std::fs::write(
+        global_dir.join("Global1/.templify.yml"),
+        "description: global_description",
+    )
+    .unwrap();
+    std::fs::create_dir_all(global_dir.join("Test1")).unwrap();
+    std::fs::write(
+        global_dir.join("Test1/.templify.yml"),
+        "description: shadowed_description",
+    )
+    .unwrap();
+
+    log::clear();
+    utils::run_successfully("tpy list");
+    log::contains_lines_in_order(&[
+        "Available templates:",
+        "Test4 - test4_description",
+        &format!("Global templates ({}):", global_dir.to_str().unwrap()),
+        "Global1 - global_description",
+    ]);
+    log::not_contains_line("Test1 - shadowed_description");
 }
@@ -1,8 +1,10 @@
+pub mod config_test;
 pub mod generate_test;
 pub mod help_test;
 pub mod init_test;
 pub mod list_test;
 pub mod load_test;
 pub mod new_test;
+pub mod placeholder_test;
 pub mod reload_test;
 pub mod version_test;
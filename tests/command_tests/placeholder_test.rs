@@ -0,0 +1,258 @@
+include!("../common/utils.rs");
+include!("../common/log.rs");
+include!("../common/fs.rs");
+
+pub fn test() {
+    utils::init_tpy();
+
+    // isolate the user config, so that the config of the developer is not used
+    let project_root = std::env::current_dir().unwrap();
+    let config_home = project_root.join(".config");
+    std::fs::create_dir_all(config_home.join("templify")).unwrap();
+    std::fs::write(
+        config_home.join("templify/config.yml"),
+        "update-check: false",
+    )
+    .unwrap();
+    let _config_home = utils::EnvVarGuard::set("XDG_CONFIG_HOME", config_home.to_str().unwrap());
+
+    log::clear();
+    utils::run_successfully("tpy placeholder");
+    log::contains_string("$$year$$");
+    log::contains_string("$$git-name$$");
+
+    log::contains_string("$$date:<param>$$");
+    log::contains_string("$$random:<param>$$");
+    log::contains_line(
+        "  $$template-name$$ - The name of the template that is used for the generation",
+    );
+    log::contains_string("$$author-email$$");
+    log::contains_string("$$git-email$$ - The email of the git user");
+
+    // test built-in placeholders
+    utils::run_successfully("tpy new Builtin -path src");
+    fs::templates_dir()
+        .dir("Builtin")
+        .file("$$name$$.txt")
+        .create_file("template: $$template-name$$ $$template-name.kebab$$")
+        .append_line("date: $$date:%Y$$")
+        .append_line("timestamp: $$timestamp:iso$$")
+        .append_line("random: [$$random:12$$]")
+        .append_line("unknown: $$foo:bar$$")
+        .append_line("uuid: $$uuid$$")
+        .append_line("uuid: $$uuid$$")
+        .append_line("project: $$project-name$$");
+    utils::run_successfully("tpy generate Builtin builtin");
+
+    let year = chrono::Local::now().format("%Y").to_string();
+    let content = std::fs::read_to_string("src/builtin.txt").unwrap();
+    assert!(content.contains("template: Builtin builtin"));
+    assert!(content.contains(&format!("date: {}", year)));
+    assert!(content.contains(&format!("timestamp: {}-", year)));
+    assert!(content.contains("unknown: $$foo:bar$$"));
+    assert!(content.contains("project: .templify-test-dir"));
+    let random = content
+        .split('[')
+        .nth(1)
+        .unwrap()
+        .split(']')
+        .next()
+        .unwrap();
+    assert_eq!(random.len(), 12);
+    assert!(random.chars().all(|c| c.is_ascii_hexdigit()));
+    let uuids = content
+        .lines()
+        .filter(|l| l.starts_with("uuid: "))
+        .collect::<Vec<&str>>();
+    assert_eq!(uuids.len(), 2);
+    assert_eq!(uuids[0].len(), "uuid: ".len() + 36);
+    assert_ne!(uuids[0], uuids[1]);
+
+    // test location placeholders
+    utils::run_successfully("tpy new Location -path src/components/$$name$$");
+    fs::templates_dir()
+        .dir("Location")
+        .file("$$name$$.ts")
+        .create_file("import api from '$$relpath:src/lib/api$$';")
+        .append_line("import sibling from '$$relpath:src/components/$$name$$/sibling$$';")
+        .append_line("dir: $$output-dir$$")
+        .append_line("file: $$file-path$$");
+    utils::run_successfully("tpy generate Location button");
+    fs::dir("src")
+        .dir("components")
+        .dir("button")
+        .file("button.ts")
+        .contains_string("import api from '../../lib/api';")
+        .contains_string("import sibling from './sibling';")
+        .contains_string("dir: src/components/button")
+        .contains_string("file: src/components/button/button.ts");
+
+    // test the deterministic clock
+    fs::file(".templifyrc.yml").create_file("timezone: utc");
+    let source_date_epoch = utils::EnvVarGuard::set("SOURCE_DATE_EPOCH", "86400");
+    utils::run_successfully("tpy new Clock -path src");
+    fs::templates_dir()
+        .dir("Clock")
+        .file("$$name$$.txt")
+        .create_file("$$year$$-$$month$$-$$day$$ $$date:%H:%M$$ $$timestamp$$ $$timestamp:iso$$");
+    utils::run_successfully("tpy generate Clock clock");
+    drop(source_date_epoch);
+    fs::dir("src")
+        .file("clock.txt")
+        .contains_string("1970-1-2 00:00 86400 1970-01-02T00:00:00+00:00");
+
+    // test that the time zone of the project config is used from a subdirectory
+    let binary_env = [
+        ("XDG_CONFIG_HOME", config_home.to_str().unwrap()),
+        ("TZ", "Asia/Tokyo"),
+    ];
+    fs::templates_dir()
+        .dir("Clock")
+        .file("$$name$$.txt")
+        .create_file("day $$day$$ $$date:%H$$");
+    std::fs::create_dir_all("src/deep").unwrap();
+    std::env::set_current_dir(project_root.join("src/deep")).unwrap();
+    let (success, _) = utils::run_binary(
+        "--now 2024-01-31T20:00:00Z generate Clock zoned",
+        "",
+        &binary_env,
+    );
+    std::env::set_current_dir(&project_root).unwrap();
+    assert!(success);
+    fs::dir("src").file("zoned.txt").has_content("day 31 20");
+
+    // test that a given time keeps its offset if no time zone is configured
+    fs::file(".templifyrc.yml").create_file("");
+    let binary_env = [
+        ("XDG_CONFIG_HOME", config_home.to_str().unwrap()),
+        ("TZ", "America/New_York"),
+    ];
+    let (success, _) = utils::run_binary(
+        "--now 2024-01-31T23:30:00+01:00 generate Clock offset",
+        "",
+        &binary_env,
+    );
+    assert!(success);
+    fs::dir("src").file("offset.txt").has_content("day 31 23");
+    let binary_env = [
+        ("XDG_CONFIG_HOME", config_home.to_str().unwrap()),
+        ("TZ", "Asia/Tokyo"),
+        ("SOURCE_DATE_EPOCH", "86400"),
+    ];
+    let (success, _) = utils::run_binary("generate Clock epoch", "", &binary_env);
+    assert!(success);
+    fs::dir("src").file("epoch.txt").has_content("day 2 00");
+
+    // test custom placeholders from the project config
+    fs::file(".templifyrc.yml")
+        .create_file("placeholders:")
+        .append_line("  company: Acme Corp")
+        .append_line("  branch:")
+        .append_line("    command: echo main")
+        .append_line("    description: The current branch")
+        .append_line("  team:")
+        .append_line("    value: Core Team");
+
+    // commands of the project config have to be confirmed
+    utils::run_failure("tpy placeholder");
+    log::contains_string("The commands of the project config were not confirmed.");
+    let interactive_env = [
+        ("XDG_CONFIG_HOME", config_home.to_str().unwrap()),
+        ("TPY_INTERACTIVE", "1"),
+    ];
+    let (success, output) = utils::run_binary("placeholder", "n\n", &interactive_env);
+    assert!(!success);
+    assert!(output.contains("  $$branch$$: echo main"));
+    let (success, output) = utils::run_binary("placeholder", "y\n", &interactive_env);
+    assert!(success);
+    assert!(output.contains("$$branch$$ - The current branch (custom): main"));
+    utils::run_failure("tpy placeholder");
+
+    // the commands are confirmed before the snippets and hooks are rendered
+    utils::run_successfully("tpy new Confirmed -path src");
+    fs::templates_dir()
+        .dir("Confirmed")
+        .file(".templify.yml")
+        .append_line("snippets:")
+        .append_line("  - id: branch")
+        .append_line("    file: branch.txt")
+        .append_line("    content: \"file: $$branch$$\"")
+        .append_line("hooks:")
+        .append_line("  post_generate:")
+        .append_line("    - echo branch=$$branch$$ > branch.log");
+    fs::file("branch.txt").create_file("// ~~branch~~");
+    let (success, _) = utils::run_binary("generate Confirmed confirmed", "y\n", &interactive_env);
+    assert!(success);
+    fs::file("branch.txt").contains_string("file: main");
+    fs::file("branch.log").contains_string("branch=main");
+
+    let (success, _) = utils::run_binary("placeholder", "a\n", &interactive_env);
+    assert!(success);
+
+    log::clear();
+    utils::run_successfully("tpy placeholder");
+    log::contains_line("$$company$$ - A custom constant (custom): Acme Corp");
+    log::contains_line("$$branch$$ - The current branch (custom): main");
+    log::contains_line("$$team$$ - A custom constant (custom): Core Team");
+
+    utils::run_successfully("tpy new Custom -path src");
+    fs::templates_dir()
+        .dir("Custom")
+        .file("$$name$$.txt")
+        .create_file("$$company$$|$$company.kebab$$|$$branch.upper$$|$$team.snake$$");
+
+    utils::run_successfully("tpy generate Custom test");
+    fs::dir("src")
+        .file("test.txt")
+        .contains_string("Acme Corp|acme-corp|MAIN|core_team");
+
+    // test that the value of a command placeholder is only resolved once per run
+    // (commands of the user config do not need a confirmation)
+    fs::file(".templifyrc.yml").remove();
+    fs::file(".config/templify/config.yml")
+        .create_file("update-check: false")
+        .append_line("placeholders:")
+        .append_line("  counter:")
+        .append_line("    command: echo run >> counter.log && echo counted");
+    fs::templates_dir()
+        .dir("Custom")
+        .file("$$name$$.txt")
+        .create_file("$$counter$$ $$counter.upper$$");
+    fs::templates_dir()
+        .dir("Custom")
+        .file("$$name$$-$$counter$$.txt")
+        .create_file("$$counter$$");
+
+    utils::run_successfully("tpy generate Custom memo");
+    fs::dir("src")
+        .file("memo.txt")
+        .contains_string("counted COUNTED");
+    fs::dir("src").file("memo-counted.txt").check_all_exists();
+    let runs = std::fs::read_to_string("counter.log").unwrap();
+    assert_eq!(runs.lines().count(), 1);
+
+    // test that errors of placeholders in files rendered by the workers are logged
+    fs::file(".config/templify/config.yml")
+        .create_file("update-check: false")
+        .append_line("placeholders:")
+        .append_line("  broken:")
+        .append_line("    command: exit 4");
+    utils::run_successfully("tpy new Broken -path src/broken");
+    for i in 0..4 {
+        fs::templates_dir()
+            .dir("Broken")
+            .file(&format!("file{}.txt", i))
+            .create_file("[$$broken$$] [$$date:%Q$$] [$$random:abc$$] [$$random:99999999999$$]");
+    }
+    log::clear();
+    utils::run_successfully("tpy generate Broken broken");
+    log::contains_string("ERROR: Custom placeholder command `exit 4` failed");
+    log::contains_line("ERROR: Invalid date format: %Q");
+    log::contains_line("ERROR: Invalid length for random: abc");
+    log::contains_line("ERROR: Invalid length for random: 99999999999 (the maximum is 1024)");
+    fs::dir("src")
+        .dir("broken")
+        .file("file3.txt")
+        .has_content("[] [] [] []");
+    fs::file(".config/templify/config.yml").create_file("update-check: false");
+}
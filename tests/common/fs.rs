@@ -159,6 +159,24 @@ pub mod fs {
             self
         }
 
+        /// Checks if the file exists and has exactly the given content
+        pub fn has_content(&mut self, content: &str) -> &mut FSItem {
+            if self.is_dir {
+                panic!("INTERNAL: has_content called on a directory");
+            }
+
+            self.check_all_exists();
+
+            let path = self.to_path();
+            let file_content = std::fs::read_to_string(&path).unwrap();
+            assert_eq!(
+                file_content, content,
+                "🚨 File {} does not have the expected content",
+                path
+            );
+            self
+        }
+
         /// Checks if the file exists and does not contain the given string
         pub fn not_contains_string(&mut self, content: &str) -> &mut FSItem {
             if self.is_dir {
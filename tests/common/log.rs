@@ -64,6 +64,39 @@ pub mod log {
         assert!(false, "🚨 Log file does not contain line: {}", line);
     }
 
+    pub fn not_contains_line(line: &str) {
+        let log_file = get_log_file();
+
+        let transformed_line = transform_string(line);
+        let file = std::fs::read_to_string(log_file).unwrap();
+        for l in file.lines() {
+            assert!(
+                transform_string(l) != transformed_line,
+                "🚨 Log file does contain line: {}",
+                line
+            );
+        }
+    }
+
+    pub fn contains_lines_in_order(lines: &[&str]) {
+        let log_file = get_log_file();
+        let file = std::fs::read_to_string(log_file).unwrap();
+
+        let mut remaining = lines.iter().map(|l| transform_string(l)).peekable();
+        for l in file.lines() {
+            if remaining.peek() == Some(&transform_string(l)) {
+                remaining.next();
+            }
+        }
+
+        let missing = remaining.collect::<Vec<String>>();
+        assert!(
+            missing.is_empty(),
+            "🚨 Log file does not contain lines in order: {:?}",
+            lines
+        );
+    }
+
     pub fn contains_string(s: &str) {
         let log_file = get_log_file();
         let file = std::fs::read_to_string(log_file).unwrap();
@@ -34,6 +34,65 @@ pub mod utils {
         execute(args)
     }
 
+    /// Run the templify binary in a separate process with the given stdin and additional
+    /// environment variables. Returns if it succeeded and its combined stdout and stderr.
+    pub fn run_binary(line: &str, stdin: &str, env: &[(&str, &str)]) -> (bool, String) {
+        let (success, stdout, stderr) = run_binary_split(line, stdin, env);
+        (success, format!("{}{}", stdout, stderr))
+    }
+
+    pub fn run_binary_split(
+        line: &str,
+        stdin: &str,
+        env: &[(&str, &str)],
+    ) -> (bool, String, String) {
+        println!("");
+        println!(" >>> {} (binary)", line);
+
+        let mut child = std::process::Command::new(env!("CARGO_BIN_EXE_templify"))
+            .args(line.split_whitespace())
+            .envs(env.iter().copied())
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        std::io::Write::write_all(child.stdin.as_mut().unwrap(), stdin.as_bytes()).unwrap();
+        let output = child.wait_with_output().unwrap();
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+        println!("{}{}", stdout, stderr);
+        (output.status.success(), stdout, stderr)
+    }
+
+    /// Sets an environment variable and restores its previous value when it is dropped,
+    /// so that a failing assertion does not leak the variable into the following tests.
+    pub struct EnvVarGuard {
+        key: String,
+        previous: Option<String>,
+    }
+
+    impl EnvVarGuard {
+        pub fn set(key: &str, value: &str) -> EnvVarGuard {
+            let previous = std::env::var(key).ok();
+            std::env::set_var(key, value);
+            EnvVarGuard {
+                key: key.to_string(),
+                previous,
+            }
+        }
+    }
+
+    impl Drop for EnvVarGuard {
+        fn drop(&mut self) {
+            match &self.previous {
+                Some(value) => std::env::set_var(&self.key, value),
+                None => std::env::remove_var(&self.key),
+            }
+        }
+    }
+
     pub fn setup() {
         init_dir();
     }
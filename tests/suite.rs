@@ -44,6 +44,16 @@ fn get_tests() -> Vec<CommandTest> {
             test: command_tests::reload_test::test,
             exit_on_failure: false,
         },
+        CommandTest {
+            name: "config_test".to_string(),
+            test: command_tests::config_test::test,
+            exit_on_failure: false,
+        },
+        CommandTest {
+            name: "placeholder_test".to_string(),
+            test: command_tests::placeholder_test::test,
+            exit_on_failure: false,
+        },
         CommandTest {
             name: "generate_test".to_string(),
             test: command_tests::generate_test::test,